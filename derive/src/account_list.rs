@@ -2,21 +2,32 @@ use proc_macro2::{Span, TokenStream};
 use proc_macro_error::abort;
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{Data, DataStruct, DataUnion, DeriveInput, Fields, Generics, Ident, Type};
+use syn::{
+    parse_quote, Data, DataStruct, DataUnion, DeriveInput, Expr, Fields, Generics, Ident, Type,
+};
 
 use easy_proc::{find_attr, ArgumentList};
 
+use crate::discriminant::{
+    discriminant_bytes, explicit_discriminant, reserved_discriminants, unique_check,
+};
 use crate::get_crate_name;
 
 #[derive(ArgumentList)]
 pub struct AccountListAttribute {
-    #[argument(default = syn::parse_str("u64").unwrap())]
+    #[argument(default = syn::parse_str("::std::num::NonZeroU64").unwrap())]
     discriminant_type: Type,
+    #[argument(presence)]
+    explicit_discriminants: bool,
+    #[argument(presence)]
+    decode: bool,
 }
 impl Default for AccountListAttribute {
     fn default() -> Self {
         Self {
             discriminant_type: syn::parse_str("::std::num::NonZeroU64").unwrap(),
+            explicit_discriminants: false,
+            decode: false,
         }
     }
 }
@@ -27,6 +38,7 @@ pub struct AccountListDerive {
     variant_idents: Vec<Ident>,
     variant_types: Vec<Type>,
     variant_discriminants: Vec<TokenStream>,
+    reserved: Vec<Expr>,
 }
 impl AccountListDerive {
     pub fn into_token_stream(self) -> TokenStream {
@@ -39,19 +51,37 @@ impl AccountListDerive {
             variant_idents,
             variant_types,
             variant_discriminants,
+            reserved,
         } = self;
         let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
         let discriminant_type = attribute.discriminant_type;
 
+        let decode_impl = if attribute.decode {
+            Self::decode_impl(
+                &crate_name,
+                &generics,
+                &ident,
+                &variant_idents,
+                &variant_types,
+            )
+        } else {
+            TokenStream::new()
+        };
+
+        let discriminant_check = unique_check(&crate_name, &variant_discriminants, &reserved);
+
         let variant_impls = variant_idents
             .into_iter()
             .zip(variant_types.into_iter())
             .zip(variant_discriminants.into_iter())
             .map(|((var_ident, ty), dis)| {
+                let bytes = discriminant_bytes(&crate_name, &discriminant_type, &dis);
                 quote! {
                     #crate_name::static_assertions::const_assert_ne!(0, #dis);
                     #[automatically_derived]
                     unsafe impl #impl_gen #crate_name::account_list::AccountListItem<#ty> for #ident #ty_gen #where_clause {
+                        const DISCRIMINANT_BYTES: &'static [u8] = #bytes;
+
                         fn discriminant() -> ::std::num::NonZeroU64{
                             ::std::num::NonZeroU64::new(#dis).unwrap()
                         }
@@ -72,12 +102,80 @@ impl AccountListDerive {
         quote! {
             #(#variant_impls)*
 
+            #discriminant_check
+
+            #decode_impl
+
             #[automatically_derived]
             impl #impl_gen #crate_name::account_list::AccountList for #ident #ty_gen #where_clause {
                 type DiscriminantCompressed = #discriminant_type;
             }
         }
     }
+
+    fn decode_impl(
+        crate_name: &TokenStream,
+        generics: &Generics,
+        ident: &Ident,
+        variant_idents: &[Ident],
+        variant_types: &[Type],
+    ) -> TokenStream {
+        let decode_arms = variant_idents.iter().zip(variant_types.iter()).map(|(var_ident, ty)| {
+            quote! {
+                if discriminant == <Self as #crate_name::account_list::AccountListItem<#ty>>::discriminant() {
+                    return ::std::result::Result::Ok(::std::option::Option::Some(Self::#var_ident(
+                        <#ty as #crate_name::borsh::BorshDeserialize>::deserialize(data)?,
+                    )));
+                }
+            }
+        });
+        let discriminant_arms = variant_idents.iter().zip(variant_types.iter()).map(|(var_ident, ty)| {
+            quote! {
+                Self::#var_ident(_) => <Self as #crate_name::account_list::AccountListItem<#ty>>::discriminant(),
+            }
+        });
+        let encode_arms = variant_idents.iter().map(|var_ident| {
+            quote! {
+                Self::#var_ident(ref account) => ::std::result::Result::Ok(
+                    #crate_name::borsh::BorshSerialize::serialize(account, writer)?,
+                ),
+            }
+        });
+        let mut generics = generics.clone();
+        let where_clause = generics.make_where_clause();
+        for ty in variant_types {
+            where_clause.predicates.push(parse_quote! {
+                #ty: #crate_name::borsh::BorshSerialize + #crate_name::borsh::BorshDeserialize
+            });
+        }
+        let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_gen #crate_name::account_list::AccountListDecode for #ident #ty_gen #where_clause {
+                #[allow(unused_variables)]
+                fn decode(
+                    discriminant: ::std::num::NonZeroU64,
+                    data: &mut &[u8],
+                ) -> #crate_name::CruiserResult<::std::option::Option<Self>> {
+                    #(#decode_arms)*
+                    ::std::result::Result::Ok(::std::option::Option::None)
+                }
+
+                fn variant_discriminant(&self) -> ::std::num::NonZeroU64 {
+                    match *self {
+                        #(#discriminant_arms)*
+                    }
+                }
+
+                fn encode(&self, writer: &mut impl ::std::io::Write) -> #crate_name::CruiserResult<()> {
+                    match *self {
+                        #(#encode_arms)*
+                    }
+                }
+            }
+        }
+    }
 }
 impl Parse for AccountListDerive {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -92,6 +190,7 @@ impl Parse for AccountListDerive {
             }
         };
 
+        let reserved = reserved_discriminants(&derive.attrs);
         let account_list_attribute =
             find_attr(derive.attrs, &Ident::new("account_list", Span::call_site()))
                 .as_ref()
@@ -118,9 +217,21 @@ impl Parse for AccountListDerive {
                     variant_types.push(unnamed.unnamed.into_iter().next().unwrap().ty);
                 }
             }
-            variant_idents.push(variant.ident);
-            let value = match variant.discriminant {
-                None => {
+            let value = match (variant.discriminant, explicit_discriminant(&variant.attrs)) {
+                (Some(_), Some(_)) => abort!(
+                    variant.ident,
+                    "Discriminant given by both `=` and `#[discriminant]`"
+                ),
+                (Some((_, discriminant)), None) | (None, Some(discriminant)) => {
+                    quote! { #discriminant }
+                }
+                (None, None) => {
+                    if account_list_attribute.explicit_discriminants {
+                        abort!(
+                            variant.ident,
+                            "`explicit_discriminants` requires a `#[discriminant = N]` attribute"
+                        );
+                    }
                     if let Some(last) = last {
                         quote! {
                             (#last) + 1
@@ -131,8 +242,8 @@ impl Parse for AccountListDerive {
                         }
                     }
                 }
-                Some((_, discriminant)) => quote! { #discriminant },
             };
+            variant_idents.push(variant.ident);
             variant_discriminants.push(value.clone());
             last = Some(value.clone());
         }
@@ -144,6 +255,7 @@ impl Parse for AccountListDerive {
             variant_idents,
             variant_types,
             variant_discriminants,
+            reserved,
         })
     }
 }
@@ -0,0 +1,58 @@
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::abort;
+use quote::quote;
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Ident, Token, Type};
+
+use easy_proc::find_attr;
+
+/// Parses a variant's `#[discriminant = <expr>]` attribute
+pub fn explicit_discriminant(attrs: &[Attribute]) -> Option<Expr> {
+    find_attr(attrs, &Ident::new("discriminant", Span::call_site())).map(|attr| {
+        let parser = |input: ParseStream| {
+            input.parse::<Token![=]>()?;
+            input.parse::<Expr>()
+        };
+        parser
+            .parse2(attr.tokens.clone())
+            .unwrap_or_else(|error| abort!(attr, "Invalid `discriminant` attribute: {}", error))
+    })
+}
+
+/// Parses an enum's `#[reserved(<expr>, ...)]` attribute
+pub fn reserved_discriminants(attrs: &[Attribute]) -> Vec<Expr> {
+    find_attr(attrs, &Ident::new("reserved", Span::call_site())).map_or_else(Vec::new, |attr| {
+        attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+            .unwrap_or_else(|error| abort!(attr, "Invalid `reserved` attribute: {}", error))
+            .into_iter()
+            .collect()
+    })
+}
+
+/// Generates a compile time check that all `discriminants` are unique and not in `reserved`
+pub fn unique_check(
+    crate_name: &TokenStream,
+    discriminants: &[TokenStream],
+    reserved: &[Expr],
+) -> TokenStream {
+    quote! {
+        const _: () = #crate_name::util::assert_unique_discriminants(
+            &[#(#discriminants,)*],
+            &[#(#reserved,)*],
+        );
+    }
+}
+
+/// Generates a `&'static [u8]` of `discriminant` compressed by `discriminant_type` at compile time
+pub fn discriminant_bytes(
+    crate_name: &TokenStream,
+    discriminant_type: &Type,
+    discriminant: &TokenStream,
+) -> TokenStream {
+    quote! {{
+        const BYTES: #crate_name::compressed_numbers::ConstBytes = <#discriminant_type as #crate_name::compressed_numbers::ConstCompressedNumber<{ #discriminant }>>::BYTES;
+        const ARRAY: [u8; BYTES.num_bytes()] = BYTES.to_array();
+        &ARRAY
+    }}
+}
@@ -9,6 +9,9 @@ use syn::{
 
 use easy_proc::{find_attr, ArgumentList};
 
+use crate::discriminant::{
+    discriminant_bytes, explicit_discriminant, reserved_discriminants, unique_check,
+};
 use crate::get_crate_name;
 use crate::log_level::LogLevel;
 
@@ -22,6 +25,8 @@ struct InstructionListAttribute {
     processor_feature: LitStr,
     #[argument(presence)]
     no_processor: bool,
+    #[argument(presence)]
+    explicit_discriminants: bool,
     account_info: AccountInfoArg,
     account_list: Type,
 }
@@ -62,6 +67,7 @@ pub struct InstructionListDerive {
     generics: Generics,
     attribute: InstructionListAttribute,
     variants: Vec<InstructionListVariant>,
+    reserved: Vec<Expr>,
 }
 impl Parse for InstructionListDerive {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -70,6 +76,7 @@ impl Parse for InstructionListDerive {
             Ident::new(InstructionListVariantAttribute::IDENT, Span::call_site());
         let derive_input: DeriveInput = input.parse()?;
 
+        let reserved = reserved_discriminants(&derive_input.attrs);
         let instruction_list_attribute = find_attr(derive_input.attrs, &instruction_list_ident)
             .as_ref()
             .map_or_else(
@@ -89,6 +96,17 @@ impl Parse for InstructionListDerive {
             .map(|variant| InstructionListVariant::from_variant(variant, &variant_attr_ident))
             .collect();
 
+        if instruction_list_attribute.explicit_discriminants {
+            for variant in &variants {
+                if variant.discriminant.is_none() {
+                    abort!(
+                        variant.ident,
+                        "`explicit_discriminants` requires a `#[discriminant = N]` attribute"
+                    );
+                }
+            }
+        }
+
         if instruction_list_attribute.no_processor {
             for variant in &variants {
                 if let Some(processor) = &variant.attribute.processor {
@@ -102,6 +120,7 @@ impl Parse for InstructionListDerive {
             generics: derive_input.generics,
             attribute: instruction_list_attribute,
             variants,
+            reserved,
         })
     }
 }
@@ -136,6 +155,11 @@ impl InstructionListDerive {
 
         let (variant_ident, variant_instruction_type, variant_discriminant, variant_processors) =
             Self::split_variants(self.variants);
+        let discriminant_check = unique_check(&crate_name, &variant_discriminant, &self.reserved);
+        let variant_discriminant_bytes = variant_discriminant
+            .iter()
+            .map(|discriminant| discriminant_bytes(&crate_name, &discriminant_type, discriminant))
+            .collect::<Vec<_>>();
 
         let processor = if self.attribute.no_processor {
             TokenStream::new()
@@ -187,6 +211,12 @@ impl InstructionListDerive {
                     }
                 }
 
+                fn discriminant_bytes(self) -> &'static [u8]{
+                    match self{
+                        #(Self::#variant_ident => #variant_discriminant_bytes,)*
+                    }
+                }
+
                 fn from_discriminant(discriminant: u64) -> Option<Self>{
                     if false{
                         ::std::unreachable!();
@@ -200,6 +230,8 @@ impl InstructionListDerive {
                 }
             }
 
+            #discriminant_check
+
             #processor
         }
     }
@@ -261,9 +293,18 @@ impl InstructionListVariant {
             }),
         );
 
+        let discriminant = match (value.discriminant, explicit_discriminant(&value.attrs)) {
+            (Some(_), Some(_)) => abort!(
+                value.ident,
+                "Discriminant given by both `=` and `#[discriminant]`"
+            ),
+            (Some((_, discriminant)), None) | (None, Some(discriminant)) => Some(discriminant),
+            (None, None) => None,
+        };
+
         Self {
             ident: value.ident,
-            discriminant: value.discriminant.map(|val| val.1),
+            discriminant,
             attribute,
         }
     }
@@ -25,14 +25,17 @@ use crate::account_argument::AccountArgumentDerive;
 use crate::account_list::AccountListDerive;
 use crate::error::ErrorDerive;
 use crate::instruction_list::InstructionListDerive;
+use crate::pda_seeder::PDASeederDerive;
 use crate::verify_account_arg_impl::VerifyAccountArgs;
 
 mod account_argument;
 mod account_list;
+mod discriminant;
 mod error;
 mod in_place;
 mod instruction_list;
 mod log_level;
+mod pda_seeder;
 mod verify_account_arg_impl;
 
 /// If no start specified starts at `1_000_000`
@@ -165,9 +168,15 @@ pub fn derive_account_argument(ts: TokenStream) -> TokenStream {
 
 /// Derives the `InstructionList` trait.
 ///
-/// TODO: Write docs for this
+/// Discriminants start at `0` and increment by variant position unless given with `= N` or `#[discriminant = N]`.
+/// Retired discriminants can be listed with `#[reserved(N, ...)]` on the enum.
+/// Duplicate or reserved discriminants fail to compile.
+/// `#[instruction_list(explicit_discriminants)]` requires every variant to have an explicit discriminant.
 #[proc_macro_error]
-#[proc_macro_derive(InstructionList, attributes(instruction_list, instruction))]
+#[proc_macro_derive(
+    InstructionList,
+    attributes(instruction_list, instruction, discriminant, reserved)
+)]
 pub fn derive_instruction_list(ts: TokenStream) -> TokenStream {
     let stream = parse_macro_input!(ts as InstructionListDerive).into_token_stream();
     #[cfg(feature = "debug_instruction_list")]
@@ -180,9 +189,24 @@ pub fn derive_instruction_list(ts: TokenStream) -> TokenStream {
 
 /// Derives the `AccountList` trait
 ///
-/// TODO: Write docs for this
+/// Discriminants start at `1` and increment by variant position unless given with `#[discriminant = N]`.
+/// Retired discriminants can be listed with `#[reserved(N, ...)]` on the enum.
+/// Duplicate, reserved, or zero discriminants fail to compile.
+/// `#[account_list(explicit_discriminants)]` requires every variant to have an explicit discriminant.
+/// `#[account_list(decode)]` also derives `AccountListDecode`, requiring every variant type to implement borsh serialization.
+/// ```ignore
+/// #[derive(AccountList)]
+/// #[account_list(explicit_discriminants)]
+/// #[reserved(2)]
+/// pub enum EscrowAccounts {
+///     #[discriminant = 1]
+///     EscrowAccount(EscrowAccount),
+///     #[discriminant = 3]
+///     EscrowConfig(EscrowConfig),
+/// }
+/// ```
 #[proc_macro_error]
-#[proc_macro_derive(AccountList)]
+#[proc_macro_derive(AccountList, attributes(account_list, discriminant, reserved))]
 pub fn derive_account_list(ts: TokenStream) -> TokenStream {
     let stream = parse_macro_input!(ts as AccountListDerive).into_token_stream();
     #[cfg(feature = "debug_account_list")]
@@ -193,6 +217,31 @@ pub fn derive_account_list(ts: TokenStream) -> TokenStream {
     stream.into()
 }
 
+/// Derives the `PDASeeder`, `StaticPDASeeder`, and `PDASeederIdl` traits and inherent `find_address` and `find_seed_set` helpers with fallible `try_` variants.
+///
+/// Seeds are the optional prefix followed by each field in declaration order, every field must implement `PDASeed`.
+/// ```ignore
+/// #[derive(Debug, PDASeeder)]
+/// #[seeder(prefix = "escrow")]
+/// struct EscrowSeeder {
+///     initializer: Pubkey,
+/// }
+/// ```
+/// | Argument | Argument Type | Description |
+/// |---|---|---|
+/// | `prefix = <$prefix:lit_str>` | optional | A literal seed placed before the fields. |
+#[proc_macro_error]
+#[proc_macro_derive(PDASeeder, attributes(seeder))]
+pub fn derive_pda_seeder(ts: TokenStream) -> TokenStream {
+    let stream = parse_macro_input!(ts as PDASeederDerive).into_token_stream();
+    #[cfg(feature = "debug_pda_seeder")]
+    {
+        println!("{}", stream);
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    stream.into()
+}
+
 /// Verifies a given type implements the proper traits
 ///
 /// TODO: Write docs for this
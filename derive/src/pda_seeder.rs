@@ -0,0 +1,206 @@
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{Data, DataEnum, DataUnion, DeriveInput, Fields, Generics, Ident, Index, LitStr, Type};
+
+use easy_proc::{find_attr, ArgumentList};
+
+use crate::get_crate_name;
+
+#[derive(ArgumentList, Default)]
+pub struct SeederAttribute {
+    prefix: Option<LitStr>,
+}
+
+pub struct PDASeederDerive {
+    generics: Generics,
+    attribute: SeederAttribute,
+    ident: Ident,
+    fields: Vec<(TokenStream, String, Type)>,
+}
+impl PDASeederDerive {
+    pub fn into_token_stream(self) -> TokenStream {
+        let crate_name = get_crate_name();
+
+        let PDASeederDerive {
+            generics,
+            attribute,
+            ident,
+            fields,
+        } = self;
+        let (impl_gen, ty_gen, where_clause) = generics.split_for_impl();
+
+        let prefix_seed = attribute.prefix.as_ref().map(|prefix| {
+            quote! { &#prefix as &dyn #crate_name::pda_seeds::PDASeed, }
+        });
+        let prefix_idl = attribute.prefix.as_ref().map(|prefix| {
+            quote! { #crate_name::pda_seeds::IdlSeed::Const { value: #prefix }, }
+        });
+        let seed_count = fields.len() + usize::from(attribute.prefix.is_some());
+        let field_accessors = fields.iter().map(|(accessor, _, _)| accessor);
+        let field_byte_accessors = field_accessors.clone();
+        let prefix_bytes = attribute
+            .prefix
+            .as_ref()
+            .map(|prefix| quote! { #prefix.as_bytes(), });
+        let field_idls = fields.iter().map(|(_, name, ty)| {
+            let ty = type_string(ty.to_token_stream());
+            quote! { #crate_name::pda_seeds::IdlSeed::Field { name: #name, ty: #ty }, }
+        });
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_gen #crate_name::pda_seeds::PDASeeder for #ident #ty_gen #where_clause {
+                fn seeds<'__a>(&'__a self) -> ::std::boxed::Box<dyn ::std::iter::Iterator<Item = &'__a dyn #crate_name::pda_seeds::PDASeed> + '__a> {
+                    let seeds: [&'__a dyn #crate_name::pda_seeds::PDASeed; #seed_count] = [
+                        #prefix_seed
+                        #(&self.#field_accessors as &dyn #crate_name::pda_seeds::PDASeed,)*
+                    ];
+                    ::std::boxed::Box::new(::std::iter::IntoIterator::into_iter(seeds))
+                }
+
+                fn write_seed_bytes<'__a>(&'__a self, buffer: &mut #crate_name::pda_seeds::SeedBuffer<'__a>) -> #crate_name::CruiserResult<usize> {
+                    #crate_name::pda_seeds::write_static_seed_bytes::<Self, #seed_count>(self, buffer)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_gen #crate_name::pda_seeds::StaticPDASeeder<#seed_count> for #ident #ty_gen #where_clause {
+                fn seed_bytes(&self) -> [&[u8]; #seed_count] {
+                    [
+                        #prefix_bytes
+                        #(::std::convert::AsRef::<[u8]>::as_ref(&self.#field_byte_accessors),)*
+                    ]
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_gen #crate_name::pda_seeds::PDASeederIdl for #ident #ty_gen #where_clause {
+                const SEEDS: &'static [#crate_name::pda_seeds::IdlSeed] = &[
+                    #prefix_idl
+                    #(#field_idls)*
+                ];
+            }
+
+            #[automatically_derived]
+            #[allow(dead_code)]
+            impl #impl_gen #ident #ty_gen #where_clause {
+                /// Finds the address and bump seed of this PDA under `program_id`
+                pub fn find_address(&self, program_id: &#crate_name::Pubkey) -> (#crate_name::Pubkey, u8) {
+                    #crate_name::pda_seeds::PDAGenerator::find_address(self, program_id)
+                }
+
+                /// Finds the address and bump seed of this PDA under `program_id`, erroring instead of panicking
+                pub fn try_find_address(&self, program_id: &#crate_name::Pubkey) -> #crate_name::CruiserResult<(#crate_name::Pubkey, u8)> {
+                    #crate_name::pda_seeds::PDAGenerator::try_find_address(self, program_id)
+                }
+
+                /// Finds the address of this PDA under `program_id` and its seed set for signing
+                pub fn find_seed_set<'__a>(self, program_id: &#crate_name::Pubkey) -> (#crate_name::Pubkey, #crate_name::pda_seeds::PDASeedSet<'__a>)
+                where
+                    Self: '__a,
+                {
+                    #crate_name::pda_seeds::PDASeedSet::find(self, program_id)
+                }
+
+                /// Finds the address of this PDA under `program_id` and its seed set for signing, erroring instead of panicking
+                pub fn try_find_seed_set<'__a>(self, program_id: &#crate_name::Pubkey) -> #crate_name::CruiserResult<(#crate_name::Pubkey, #crate_name::pda_seeds::PDASeedSet<'__a>)>
+                where
+                    Self: '__a,
+                {
+                    #crate_name::pda_seeds::PDASeedSet::try_find(self, program_id)
+                }
+            }
+        }
+    }
+}
+/// Formats a type independently of how `proc_macro2` displays tokens.
+/// Spaces only separate words and follow `,` and `;`, so `[u8 ; 2]` becomes `[u8; 2]`.
+fn type_string(tokens: TokenStream) -> String {
+    fn write_tokens(tokens: TokenStream, out: &mut String) {
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::None => ("", ""),
+                    };
+                    out.push_str(open);
+                    write_tokens(group.stream(), out);
+                    if out.ends_with(' ') {
+                        out.pop();
+                    }
+                    out.push_str(close);
+                }
+                TokenTree::Punct(punct) => {
+                    out.push(punct.as_char());
+                    if matches!(punct.as_char(), ',' | ';') {
+                        out.push(' ');
+                    }
+                }
+                TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                    if out.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                        out.push(' ');
+                    }
+                    out.push_str(&token.to_string());
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write_tokens(tokens, &mut out);
+    out
+}
+
+impl Parse for PDASeederDerive {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let derive: DeriveInput = input.parse()?;
+        let struct_data = match derive.data {
+            Data::Struct(data) => data,
+            Data::Enum(DataEnum { enum_token, .. }) => {
+                abort!(enum_token, "`#[derive(PDASeeder)]` only supports structs")
+            }
+            Data::Union(DataUnion { union_token, .. }) => {
+                abort!(union_token, "`#[derive(PDASeeder)]` only supports structs")
+            }
+        };
+
+        let attribute = find_attr(derive.attrs, &Ident::new("seeder", Span::call_site()))
+            .as_ref()
+            .map(SeederAttribute::parse_arguments)
+            .unwrap_or_default();
+
+        let fields = match struct_data.fields {
+            Fields::Named(named) => named
+                .named
+                .into_iter()
+                .map(|field| {
+                    let ident = field.ident.unwrap();
+                    let name = ident.to_string();
+                    (quote! { #ident }, name, field.ty)
+                })
+                .collect(),
+            Fields::Unnamed(unnamed) => unnamed
+                .unnamed
+                .into_iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let accessor = Index::from(index);
+                    (quote! { #accessor }, index.to_string(), field.ty)
+                })
+                .collect(),
+            Fields::Unit => Vec::new(),
+        };
+
+        Ok(Self {
+            generics: derive.generics,
+            attribute,
+            ident: derive.ident,
+            fields,
+        })
+    }
+}
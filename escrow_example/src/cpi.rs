@@ -20,9 +20,7 @@ impl<'a, AI> InitEscrow<'a, AI> {
         amount: u64,
     ) -> CruiserResult<Self> {
         let mut data = Vec::with_capacity(8 + 8);
-        EscrowInstructions::InitEscrow
-            .discriminant_compressed()
-            .serialize(&mut data)?;
+        data.extend_from_slice(EscrowInstructions::InitEscrow.discriminant_bytes());
         amount.serialize(&mut data)?;
         Ok(Self {
             accounts: [
@@ -93,9 +91,7 @@ impl<'a, AI> Exchange<'a, AI> {
         amount: u64,
     ) -> CruiserResult<Self> {
         let mut data = Vec::with_capacity(8 + 8);
-        EscrowInstructions::Exchange
-            .discriminant_compressed()
-            .serialize(&mut data)?;
+        data.extend_from_slice(EscrowInstructions::Exchange.discriminant_bytes());
         amount.serialize(&mut data)?;
         Ok(Self {
             accounts: [
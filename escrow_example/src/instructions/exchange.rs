@@ -5,7 +5,7 @@ use cruiser::account_types::data_account::DataAccount;
 use cruiser::account_types::seeds::{Find, Seeds};
 use cruiser::borsh::{BorshDeserialize, BorshSerialize};
 use cruiser::instruction::Instruction;
-use cruiser::spl::token::{Owner, TokenAccount, TokenProgram};
+use cruiser::spl::token::{Owner, TokenAccount, TokenChecks, TokenProgram};
 use cruiser::{borsh, AccountInfo};
 
 pub struct Exchange;
@@ -27,13 +27,16 @@ where
     taker: AI,
     #[validate(writable, data = Owner(self.taker.key()))]
     taker_send_token_account: TokenAccount<AI>,
-    #[validate(writable)]
+    #[validate(writable, data = TokenChecks {
+        mint: Some(&self.temp_token_account.mint),
+        ..TokenChecks::default()
+    })]
     taker_receive_token_account: TokenAccount<AI>,
-    #[validate(writable, key = &self.escrow_account.temp_token_account)]
+    #[validate(writable, key = &self.escrow_account.try_data()?.temp_token_account)]
     temp_token_account: TokenAccount<AI>,
-    #[validate(writable, key = &self.escrow_account.initializer)]
+    #[validate(writable, key = &self.escrow_account.try_data()?.initializer)]
     initializer: AI,
-    #[validate(writable, key = &self.escrow_account.initializer_token_to_receive)]
+    #[validate(writable, key = &self.escrow_account.try_data()?.initializer_token_to_receive)]
     initializer_token_account: TokenAccount<AI>,
     #[validate(writable)]
     escrow_account: CloseAccount<AI, DataAccount<AI, EscrowAccounts, EscrowAccount>>,
@@ -77,11 +80,12 @@ mod processor {
             data: <Self as Instruction<AI>>::Data,
             accounts: &mut <Self as Instruction<AI>>::Accounts,
         ) -> CruiserResult<()> {
-            if data.amount != accounts.escrow_account.expected_amount {
+            let expected_amount = accounts.escrow_account.try_data()?.expected_amount;
+            if data.amount != expected_amount {
                 return Err(GenericError::Custom {
                     error: format!(
                         "Amount (`{}`) did not equal expected (`{}`)",
-                        data.amount, accounts.escrow_account.expected_amount
+                        data.amount, expected_amount
                     ),
                 }
                 .into());
@@ -93,7 +97,7 @@ mod processor {
                 &accounts.taker_send_token_account,
                 &accounts.initializer_token_account,
                 &accounts.taker,
-                accounts.escrow_account.expected_amount,
+                expected_amount,
                 empty(),
             )?;
 
@@ -51,7 +51,6 @@ mod processor {
     use crate::EscrowPDASeeder;
     use cruiser::account_argument::Single;
     use cruiser::instruction::InstructionProcessor;
-    use cruiser::pda_seeds::PDAGenerator;
     use cruiser::{msg, CruiserResult, Pubkey};
     use std::iter::empty;
 
@@ -10,7 +10,7 @@ use cruiser::account_list::AccountList;
 use cruiser::borsh::{BorshDeserialize, BorshSerialize};
 use cruiser::instruction_list::InstructionList;
 use cruiser::on_chain_size::{OnChainSize, OnChainStaticSize};
-use cruiser::pda_seeds::{PDASeed, PDASeeder};
+use cruiser::pda_seeds::PDASeeder;
 use cruiser::{borsh, Pubkey};
 
 #[cfg(feature = "entrypoint")]
@@ -41,10 +41,6 @@ impl OnChainSize<()> for EscrowAccount {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PDASeeder)]
+#[seeder(prefix = "escrow")]
 struct EscrowPDASeeder;
-impl PDASeeder for EscrowPDASeeder {
-    fn seeds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn PDASeed> + 'a> {
-        Box::new([&"escrow" as &dyn PDASeed].into_iter())
-    }
-}
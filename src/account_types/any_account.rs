@@ -0,0 +1,292 @@
+//! An account owned by the current program that can be any type in an account list
+
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::account_list::AccountListDecode;
+use crate::compressed_numbers::CompressedNumber;
+use crate::{AccountInfo, CruiserResult, GenericError};
+
+// verify_account_arg_impl! {
+//     mod any_account_check<AI>{
+//         <AI, AL> AnyAccount<AI, AL> where AI: AccountInfo, AL: AccountListDecode{
+//             from: [()];
+//             validate: [()];
+//             multi: [<I> I where AI: MultiIndexable<AI, I>];
+//             single: [<I> I where AI: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// An account owned by the current program whose type is determined by its discriminant.
+/// Derefs to the account list `AL` which can be matched on for the account type.
+/// `AL` must be derived with `#[account_list(decode)]`.
+///
+/// The contained account, including its discriminant, is written back if the account is writable.
+/// Changing to an account type with larger data requires the account to already be large enough.
+pub struct AnyAccount<AI, AL> {
+    /// The [`AccountInfo`] of this account.
+    pub info: AI,
+    data: AL,
+}
+impl<AI, AL> AnyAccount<AI, AL> {
+    /// Returns the account list variant
+    #[must_use]
+    pub fn into_inner(self) -> AL {
+        self.data
+    }
+}
+impl<AI, AL> Debug for AnyAccount<AI, AL>
+where
+    AI: Debug,
+    AL: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnyAccount")
+            .field("info", &self.info)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+impl<AI, AL> Deref for AnyAccount<AI, AL> {
+    type Target = AL;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+impl<AI, AL> DerefMut for AnyAccount<AI, AL> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+impl<AI, AL> AccountArgument for AnyAccount<AI, AL>
+where
+    AI: AccountInfo,
+    AL: AccountListDecode,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        if self.info.is_writable() {
+            let mut data_ref = self.info.data_mut();
+            let mut data = &mut *data_ref;
+            AL::DiscriminantCompressed::from_number(self.data.variant_discriminant())
+                .serialize(&mut data)?;
+            self.data.encode(&mut data)?;
+        }
+        self.info.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.info.add_keys(add)
+    }
+}
+impl<AI, AL> FromAccounts<()> for AnyAccount<AI, AL>
+where
+    AI: AccountInfo,
+    AL: AccountListDecode,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, arg)?;
+        let info_data = info.data();
+        let mut data: &[u8] = &info_data;
+        let discriminant = AL::DiscriminantCompressed::deserialize(&mut data)?.into_number();
+        let value = match AL::decode(discriminant, &mut data)? {
+            Some(value) => value,
+            None => {
+                return Err(GenericError::UnknownAccountDiscriminant {
+                    account: *info.key(),
+                    discriminant: discriminant.get(),
+                }
+                .into())
+            }
+        };
+        drop(info_data);
+        Ok(Self { info, data: value })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<AI, AL> ValidateArgument<()> for AnyAccount<AI, AL>
+where
+    AI: AccountInfo,
+    AL: AccountListDecode,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.info.validate(program_id, arg)?;
+        let owner = *self.info.owner();
+        if &owner == program_id {
+            Ok(())
+        } else {
+            Err(GenericError::AccountOwnerNotEqual {
+                account: *self.info.key(),
+                owner,
+                expected_owner: vec![*program_id],
+            }
+            .into())
+        }
+    }
+}
+impl<AI, AL, I> MultiIndexable<I> for AnyAccount<AI, AL>
+where
+    AI: AccountInfo + MultiIndexable<I>,
+    AL: AccountListDecode,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, AL, I> SingleIndexable<I> for AnyAccount<AI, AL>
+where
+    AI: AccountInfo + SingleIndexable<I>,
+    AL: AccountListDecode,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::account_list::{AccountList, AccountListItem};
+    use crate::SolanaAccountInfo;
+
+    use super::*;
+
+    #[derive(AccountList, Debug, Eq, PartialEq)]
+    #[account_list(decode)]
+    enum TestAccounts {
+        Small(Small),
+        Large(Large),
+    }
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Eq, PartialEq)]
+    struct Small {
+        value: u8,
+    }
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Eq, PartialEq)]
+    struct Large {
+        value: u64,
+        key: Pubkey,
+    }
+
+    fn account_data(account: &TestAccounts) -> Vec<u8> {
+        let mut data = Vec::new();
+        <TestAccounts as AccountList>::DiscriminantCompressed::from_number(
+            account.variant_discriminant(),
+        )
+        .serialize(&mut data)
+        .unwrap();
+        account.encode(&mut data).unwrap();
+        data
+    }
+
+    fn read(program_id: &Pubkey, info: SolanaAccountInfo) -> CruiserResult<TestAccounts> {
+        let mut account: AnyAccount<_, TestAccounts> =
+            FromAccounts::from_accounts(program_id, &mut [info].into_iter(), ())?;
+        account.validate(program_id, ())?;
+        Ok(account.into_inner())
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for account in [
+            TestAccounts::Small(Small { value: 3 }),
+            TestAccounts::Large(Large {
+                value: 4,
+                key: Pubkey::new_unique(),
+            }),
+        ] {
+            let data = account_data(&account);
+            let mut reader = &data[..];
+            let discriminant =
+                <TestAccounts as AccountList>::DiscriminantCompressed::deserialize(&mut reader)
+                    .unwrap()
+                    .into_number();
+            assert_eq!(
+                TestAccounts::decode(discriminant, &mut reader).unwrap(),
+                Some(account)
+            );
+            assert!(reader.is_empty());
+        }
+        let discriminant_bytes = <TestAccounts as AccountListItem<Small>>::DISCRIMINANT_BYTES;
+        assert_eq!(
+            discriminant_bytes,
+            &account_data(&TestAccounts::Small(Small { value: 0 }))[..discriminant_bytes.len()]
+        );
+    }
+
+    #[test]
+    fn write_back_test() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let large = TestAccounts::Large(Large {
+            value: 4,
+            key: Pubkey::new_unique(),
+        });
+        let small = TestAccounts::Small(Small { value: 3 });
+        for is_writable in [true, false] {
+            let mut data = account_data(&large);
+            let mut lamports = 0;
+            let info = SolanaAccountInfo::new(
+                &key,
+                false,
+                is_writable,
+                &mut lamports,
+                &mut data,
+                &program_id,
+                false,
+                0,
+            );
+            let mut account: AnyAccount<_, TestAccounts> =
+                FromAccounts::from_accounts(&program_id, &mut [info.clone()].into_iter(), ())
+                    .unwrap();
+            account.validate(&program_id, ()).unwrap();
+            assert_eq!(*account, large);
+            *account = TestAccounts::Small(Small { value: 3 });
+            account.write_back(&program_id).unwrap();
+            let expected = if is_writable { &small } else { &large };
+            assert_eq!(&read(&program_id, info).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn unknown_discriminant_test() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut data = vec![u8::MAX; 16];
+        let mut lamports = 0;
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert!(read(&program_id, info).is_err());
+    }
+}
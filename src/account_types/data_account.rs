@@ -1,15 +1,19 @@
 //! An account owned by the current program
 
+use std::cell::UnsafeCell;
 use std::fmt::{Debug, Formatter};
-use std::ops::{Deref, DerefMut};
+use std::marker::PhantomData;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
-use crate::account_argument::{AccountArgument, MultiIndexable, SingleIndexable};
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
 use crate::account_list::AccountListItem;
-use crate::account_types::discriminant_account::DiscriminantAccount;
-use crate::{AccountInfo, CruiserResult};
+use crate::util::assert::assert_discriminant;
+use crate::{AccountInfo, CruiserResult, GenericError};
 
 // verify_account_arg_impl! {
 //     mod data_account_check<AI>{
@@ -21,81 +25,308 @@ use crate::{AccountInfo, CruiserResult};
 //         {
 //             from: [()];
 //             validate: [()];
-//             multi: [<T> T where DiscriminantAccount<AI, AL, D>: MultiIndexable<AI, T>];
-//             single: [<T> T where DiscriminantAccount<AI, AL, D>: SingleIndexable<AI, T>];
+//             multi: [<T> T where AI: MultiIndexable<AI, T>];
+//             single: [<T> T where AI: SingleIndexable<AI, T>];
 //         }
 //     }
 // }
 
 /// An account owned by the current program.
+/// Writable accounts are deserialized on validation, read-only accounts on first access.
+/// The data is only written back if mutably accessed and the account is writable.
 ///
 /// - `AL`: The [`AccountList`](crate::account_list::AccountList) that is valid for `A`
 /// - `A` The account data, `AL` must implement [`AccountListItem<A>`](AccountListItem)
-#[derive(AccountArgument)]
-#[account_argument(account_info = AI, generics = [where AI: AccountInfo, D: BorshSerialize + BorshDeserialize])]
+///
+/// Unlike [`DiscriminantAccount`](crate::account_types::discriminant_account::DiscriminantAccount) this does not [`Deref`](std::ops::Deref) to the data as reading it can fail,
+/// access it through [`DataAccount::try_data`] and [`DataAccount::try_data_mut`] instead.
 pub struct DataAccount<AI, AL, D>
 where
     AL: AccountListItem<D>,
 {
-    #[validate(owner = program_id)]
-    account: DiscriminantAccount<AI, AL, D>,
+    /// The [`AccountInfo`] of this account.
+    pub info: AI,
+    /// Only ever written while [`None`] so no references into it can exist at that time
+    data: UnsafeCell<Option<D>>,
+    dirty: bool,
+    phantom_al: PhantomData<fn() -> AL>,
+}
+impl<AI, AL, D> DataAccount<AI, AL, D>
+where
+    AI: AccountInfo,
+    AL: AccountListItem<D>,
+    D: BorshDeserialize,
+{
+    /// Gets the data, deserializing it if not yet accessed
+    pub fn try_data(&self) -> CruiserResult<&D> {
+        // Safety: `data` is only written below while `None`
+        if let Some(data) = unsafe { &*self.data.get() } {
+            return Ok(data);
+        }
+        let data = Self::deserialize_data(&self.info)?;
+        // Safety: `data` is `None` so no references into it exist
+        Ok(unsafe { (*self.data.get()).insert(data) })
+    }
+
+    /// Gets the data mutably, deserializing it if not yet accessed. Marks the data to be written back.
+    pub fn try_data_mut(&mut self) -> CruiserResult<&mut D> {
+        let data = match self.data.get_mut() {
+            Some(data) => data,
+            data @ None => data.insert(Self::deserialize_data(&self.info)?),
+        };
+        self.dirty = true;
+        Ok(data)
+    }
+
+    fn deserialize_data(info: &AI) -> CruiserResult<D> {
+        let data = info.data();
+        let discriminant_len = AL::DISCRIMINANT_BYTES.len();
+        let mut data = data
+            .get(discriminant_len..)
+            .ok_or(GenericError::NotEnoughData {
+                needed: discriminant_len,
+                remaining: data.len(),
+            })?;
+        Ok(D::deserialize(&mut data)?)
+    }
+}
+impl<AI, AL, D> DataAccount<AI, AL, D>
+where
+    AL: AccountListItem<D>,
+{
+    /// Returns true if the data has been mutably accessed and will be written back
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
 }
 impl<AI, AL, D> Debug for DataAccount<AI, AL, D>
 where
+    AI: Debug,
     AL: AccountListItem<D>,
-    DiscriminantAccount<AI, AL, D>: Debug,
+    D: Debug,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ProgramAccount")
-            .field("account", &self.account)
+        f.debug_struct("DataAccount")
+            .field("info", &self.info)
+            // Safety: `data` is only written while `None` and this borrow ends before any write
+            .field("data", unsafe { &*self.data.get() })
+            .field("dirty", &self.dirty)
             .finish()
     }
 }
-impl<AI, AL, D> Deref for DataAccount<AI, AL, D>
+impl<AI, AL, D> AccountArgument for DataAccount<AI, AL, D>
 where
+    AI: AccountInfo,
     AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
 {
-    type Target = DiscriminantAccount<AI, AL, D>;
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        if self.dirty && self.info.is_writable() {
+            if let Some(data) = self.data.into_inner() {
+                let mut data_ref = self.info.data_mut();
+                data.serialize(&mut &mut data_ref[AL::DISCRIMINANT_BYTES.len()..])?;
+            }
+        }
+        self.info.write_back(program_id)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.account
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.info.add_keys(add)
     }
 }
-impl<AI, AL, D> DerefMut for DataAccount<AI, AL, D>
+impl<AI, AL, D> FromAccounts<()> for DataAccount<AI, AL, D>
 where
+    AI: AccountInfo,
     AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
 {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.account
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        Ok(Self {
+            info: AI::from_accounts(program_id, infos, arg)?,
+            data: UnsafeCell::new(None),
+            dirty: false,
+            phantom_al: PhantomData,
+        })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
     }
 }
-impl<AI, AL, D, T> MultiIndexable<T> for DataAccount<AI, AL, D>
+impl<AI, AL, D> ValidateArgument<()> for DataAccount<AI, AL, D>
 where
     AI: AccountInfo,
     AL: AccountListItem<D>,
     D: BorshSerialize + BorshDeserialize,
-    DiscriminantAccount<AI, AL, D>: MultiIndexable<T>,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.info.validate(program_id, arg)?;
+        let owner = *self.info.owner();
+        if &owner != program_id {
+            return Err(GenericError::AccountOwnerNotEqual {
+                account: *self.info.key(),
+                owner,
+                expected_owner: vec![*program_id],
+            }
+            .into());
+        }
+        assert_discriminant::<_, AL, D>(&self.info)?;
+        if self.info.is_writable() {
+            self.try_data()?;
+        }
+        Ok(())
+    }
+}
+impl<AI, AL, D, T> MultiIndexable<T> for DataAccount<AI, AL, D>
+where
+    AI: AccountInfo + MultiIndexable<T>,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
 {
     fn index_is_signer(&self, indexer: T) -> CruiserResult<bool> {
-        self.account.index_is_signer(indexer)
+        self.info.index_is_signer(indexer)
     }
 
     fn index_is_writable(&self, indexer: T) -> CruiserResult<bool> {
-        self.account.index_is_writable(indexer)
+        self.info.index_is_writable(indexer)
     }
 
     fn index_is_owner(&self, owner: &Pubkey, indexer: T) -> CruiserResult<bool> {
-        self.account.index_is_owner(owner, indexer)
+        self.info.index_is_owner(owner, indexer)
     }
 }
 impl<AI, AL, D, T> SingleIndexable<T> for DataAccount<AI, AL, D>
 where
-    AI: AccountInfo,
+    AI: AccountInfo + SingleIndexable<T>,
     AL: AccountListItem<D>,
     D: BorshSerialize + BorshDeserialize,
-    DiscriminantAccount<AI, AL, D>: SingleIndexable<T, AccountInfo = AI>,
 {
     fn index_info(&self, indexer: T) -> CruiserResult<&AI> {
-        self.account.index_info(indexer)
+        self.info.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::account_list::AccountList;
+    use crate::SolanaAccountInfo;
+
+    use super::*;
+
+    #[derive(AccountList)]
+    enum TestAccounts {
+        Data(TestData),
+    }
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Eq, PartialEq)]
+    struct TestData {
+        value: u64,
+    }
+
+    fn account_data(value: u64) -> Vec<u8> {
+        let mut data = <TestAccounts as AccountListItem<TestData>>::DISCRIMINANT_BYTES.to_vec();
+        TestData { value }.serialize(&mut data).unwrap();
+        data
+    }
+
+    fn stored_value(info: &SolanaAccountInfo) -> u64 {
+        u64::deserialize(&mut &info.data.borrow()[DISCRIMINANT_LEN..]).unwrap()
+    }
+
+    const DISCRIMINANT_LEN: usize =
+        <TestAccounts as AccountListItem<TestData>>::DISCRIMINANT_BYTES.len();
+
+    #[test]
+    fn write_back_test() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        for (is_writable, mutate, expected) in [
+            (true, true, 2),
+            (true, false, 3),
+            (false, true, 3),
+            (false, false, 3),
+        ] {
+            let mut data = account_data(1);
+            let mut lamports = 0;
+            let info = SolanaAccountInfo::new(
+                &key,
+                false,
+                is_writable,
+                &mut lamports,
+                &mut data,
+                &program_id,
+                false,
+                0,
+            );
+            let mut account: DataAccount<_, TestAccounts, TestData> =
+                FromAccounts::from_accounts(&program_id, &mut [info.clone()].into_iter(), ())
+                    .unwrap();
+            account.validate(&program_id, ()).unwrap();
+            assert_eq!(account.try_data().unwrap().value, 1);
+            if mutate {
+                account.try_data_mut().unwrap().value = 2;
+            }
+            assert_eq!(account.is_dirty(), mutate);
+            // Changed behind the account's back to detect any reserialization
+            3u64.serialize(&mut &mut info.data.borrow_mut()[DISCRIMINANT_LEN..])
+                .unwrap();
+            account.write_back(&program_id).unwrap();
+            assert_eq!(stored_value(&info), expected);
+        }
+    }
+
+    #[test]
+    fn malformed_data_test() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        for is_writable in [true, false] {
+            let mut data = account_data(1);
+            data.truncate(DISCRIMINANT_LEN + 4);
+            let mut lamports = 0;
+            let info = SolanaAccountInfo::new(
+                &key,
+                false,
+                is_writable,
+                &mut lamports,
+                &mut data,
+                &program_id,
+                false,
+                0,
+            );
+            let mut account: DataAccount<_, TestAccounts, TestData> =
+                FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ()).unwrap();
+            // Writable accounts fail validation instead of panicking on access
+            assert_eq!(account.validate(&program_id, ()).is_err(), is_writable);
+            assert!(account.try_data().is_err());
+        }
+    }
+
+    #[test]
+    fn short_data_test() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut data = account_data(1);
+        let mut lamports = 0;
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        let account: DataAccount<_, TestAccounts, TestData> =
+            FromAccounts::from_accounts(&program_id, &mut [info.clone()].into_iter(), ()).unwrap();
+        // Shorter than the discriminant, as if reallocated after validation
+        info.data.replace(&mut []);
+        assert!(account.try_data().is_err());
     }
 }
@@ -9,7 +9,7 @@ use crate::account_argument::{
 };
 use crate::account_list::AccountListItem;
 use crate::account_types::PhantomAccount;
-use crate::compressed_numbers::CompressedNumber;
+use crate::util::assert::assert_discriminant;
 use crate::AccountInfo;
 use crate::{CruiserAccountInfo, CruiserResult, GenericError};
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -91,7 +91,7 @@ where
 
     fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
         let mut data_ref = self.info.data_mut();
-        let mut data = &mut data_ref[AL::compressed_discriminant().num_bytes()..];
+        let mut data = &mut data_ref[AL::DISCRIMINANT_BYTES.len()..];
         self.data.serialize(&mut data)?;
         drop(data_ref);
         self.info.write_back(program_id)
@@ -113,7 +113,7 @@ where
         arg: (),
     ) -> CruiserResult<Self> {
         let info = AI::from_accounts(program_id, infos, arg)?;
-        let data = D::deserialize(&mut &info.data()[AL::compressed_discriminant().num_bytes()..])?;
+        let data = D::deserialize(&mut &info.data()[AL::DISCRIMINANT_BYTES.len()..])?;
         Ok(Self {
             info,
             phantom_al: PhantomAccount::default(),
@@ -157,17 +157,7 @@ where
 {
     fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
         self.info.validate(program_id, arg)?;
-        let discriminant = AL::DiscriminantCompressed::deserialize(&mut &*self.info.data())?;
-        if discriminant == AL::compressed_discriminant() {
-            Ok(())
-        } else {
-            Err(GenericError::MismatchedDiscriminant {
-                account: *self.info.key(),
-                received: discriminant.into_number().get(),
-                expected: AL::discriminant(),
-            }
-            .into())
-        }
+        assert_discriminant::<_, AL, D>(&self.info)
     }
 }
 /// Writes the discriminant to the account rather than verifying it
@@ -181,7 +171,15 @@ where
 {
     fn validate(&mut self, program_id: &Pubkey, _arg: WriteDiscriminant) -> CruiserResult<()> {
         self.info.validate(program_id, ())?;
-        AL::compressed_discriminant().serialize(&mut &mut *self.info.data_mut())?;
+        let mut data = self.info.data_mut();
+        let remaining = data.len();
+        let discriminant =
+            data.get_mut(..AL::DISCRIMINANT_BYTES.len())
+                .ok_or(GenericError::NotEnoughData {
+                    needed: AL::DISCRIMINANT_BYTES.len(),
+                    remaining,
+                })?;
+        discriminant.copy_from_slice(AL::DISCRIMINANT_BYTES);
         Ok(())
     }
 }
@@ -5,7 +5,6 @@ use std::ops::{Deref, DerefMut};
 
 use crate::cpi::CPI;
 use borsh::{BorshDeserialize, BorshSerialize};
-use cruiser::util::short_iter::ShortIter;
 use solana_program::pubkey::Pubkey;
 use solana_program::rent::Rent;
 use solana_program::sysvar::Sysvar;
@@ -13,11 +12,11 @@ use solana_program::sysvar::Sysvar;
 use crate::account_argument::{AccountArgument, MultiIndexable, SingleIndexable, ValidateArgument};
 use crate::account_list::AccountListItem;
 use crate::account_types::discriminant_account::{DiscriminantAccount, WriteDiscriminant};
+use crate::account_types::seeds::StoredBumpSeed;
 use crate::account_types::system_program::{Create, SystemProgram};
-use crate::compressed_numbers::CompressedNumber;
-use crate::pda_seeds::PDASeedSet;
-use crate::CruiserResult;
+use crate::pda_seeds::{PDAGenerator, PDASeedSet};
 use crate::{AccountInfo, ToSolanaAccountInfo};
+use crate::{CruiserResult, GenericError};
 
 // verify_account_arg_impl! {
 //     mod init_account_check <AI> {
@@ -30,7 +29,10 @@ use crate::{AccountInfo, ToSolanaAccountInfo};
 //                 /// The initial value for the account's data
 //                 D;
 //             ];
-//             validate: [<'a, 'b, C> InitArgs<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI];
+//             validate: [
+//                 <'a, 'b, C> InitArgs<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy;
+//                 <'a, 'b, C> StoreBump<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy, D: StoredBumpSeed;
+//             ];
 //             multi: [(); AllAny];
 //             single: [()];
 //         }
@@ -56,7 +58,13 @@ pub struct InitArgs<'a, AI, C> {
     pub cpi: C,
 }
 
+/// Initializes the account with [`InitArgs`] and records the canonical bump seed of [`InitArgs::account_seeds`] in the account's data.
+/// Finds the canonical bump seed to verify the account address, failing if the account is not at the canonical address.
+#[derive(Debug)]
+pub struct StoreBump<'a, AI, C>(pub InitArgs<'a, AI, C>);
+
 /// Initializes a given account to be rent exempt and owned by the current program.
+/// If the account already holds lamports the shortfall is transferred and the account is allocated and assigned instead of created.
 ///
 /// - `AL`: The [`AccountList`](crate::account_list::AccountList) that is valid for `A`
 /// - `A` The account data, `AL` must implement [`AccountListItem<A>`](AccountListItem)
@@ -108,24 +116,16 @@ where
     AI: ToSolanaAccountInfo<'b>,
     AL: AccountListItem<D>,
     D: BorshSerialize + BorshDeserialize,
-    C: CPI,
+    C: CPI + Copy,
 {
     fn validate(&mut self, program_id: &Pubkey, arg: InitArgs<'a, AI, C>) -> CruiserResult<()> {
         let rent = match arg.rent {
             None => Rent::get()?,
             Some(rent) => rent,
         }
-        .minimum_balance(AL::compressed_discriminant().num_bytes() as usize + arg.space);
-
-        let mut seeds = ShortIter::<_, 2>::new();
-        if let Some(funder_seeds) = arg.funder_seeds {
-            seeds.push(funder_seeds);
-        }
-        if let Some(account_seeds) = arg.account_seeds {
-            seeds.push(account_seeds);
-        }
+        .minimum_balance(AL::DISCRIMINANT_BYTES.len() + arg.space);
 
-        arg.system_program.create_account(
+        arg.system_program.create_or_allocate_account(
             arg.cpi,
             &Create {
                 funder: arg.funder,
@@ -134,11 +134,36 @@ where
                 space: arg.space as u64,
                 owner: program_id,
             },
-            seeds,
+            arg.funder_seeds,
+            arg.account_seeds,
         )?;
         self.account.validate(program_id, WriteDiscriminant)
     }
 }
+impl<'a, 'b, AI, AL, D, C> ValidateArgument<StoreBump<'a, AI, C>> for InitAccount<AI, AL, D>
+where
+    AI: ToSolanaAccountInfo<'b>,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize + StoredBumpSeed,
+    C: CPI + Copy,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: StoreBump<'a, AI, C>) -> CruiserResult<()> {
+        let bump_seed = match arg.0.account_seeds {
+            Some(account_seeds) => account_seeds
+                .seeder()
+                .verify_address_find_nonce(program_id, self.info.key())?,
+            None => {
+                return Err(GenericError::MissingAccountSeeds {
+                    account: *self.info.key(),
+                }
+                .into())
+            }
+        };
+        self.validate(program_id, arg.0)?;
+        self.account.set_bump_seed(bump_seed);
+        Ok(())
+    }
+}
 impl<'a, AI, AL, D, T> MultiIndexable<T> for InitAccount<AI, AL, D>
 where
     AI: AccountInfo,
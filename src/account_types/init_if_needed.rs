@@ -0,0 +1,352 @@
+//! Initializes an account if it does not yet exist
+
+use std::fmt::{Debug, Formatter};
+use std::iter::once;
+use std::ops::{Deref, DerefMut};
+
+use crate::cpi::CPI;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::account_list::AccountListItem;
+use crate::account_types::discriminant_account::DiscriminantAccount;
+use crate::account_types::init_account::{InitAccount, InitArgs, StoreBump};
+use crate::account_types::seeds::StoredBumpSeed;
+use crate::pda_seeds::PDASeedSet;
+use crate::util::assert::assert_discriminant;
+use crate::{AccountInfo, CruiserResult, ToSolanaAccountInfo};
+
+// verify_account_arg_impl! {
+//     mod init_if_needed_check<AI>{
+//         <AI, AL, D> InitIfNeeded<AI, AL, D>
+//         where
+//             AI: AccountInfo,
+//             AL: AccountListItem<D>,
+//             D: BorshSerialize + BorshDeserialize,
+//         {
+//             from: [
+//                 /// The initial value of this account if it needs to be initialized
+//                 D;
+//             ];
+//             validate: [
+//                 <'a, 'b, C> InitArgs<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy;
+//                 <'a, 'b, C> StoreBump<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy, D: StoredBumpSeed;
+//             ];
+//             multi: [(); AllAny];
+//             single: [()];
+//         }
+//     }
+// }
+
+/// An account that is initialized if not owned by the current program, otherwise read as an existing account of type `D`.
+/// Use [`InitIfNeeded::was_initialized`] to check which happened.
+/// Existing accounts are checked against [`InitArgs::account_seeds`] when given.
+// TODO: use AccountArgument trait for impl when enums supported
+pub enum InitIfNeeded<AI, AL, D>
+where
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    /// The account did not exist and is initialized
+    Init(InitAccount<AI, AL, D>),
+    /// The account already existed with the correct discriminant
+    Existing(DiscriminantAccount<AI, AL, D>),
+}
+impl<AI, AL, D> InitIfNeeded<AI, AL, D>
+where
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    /// Returns true if the account is being initialized by this instruction
+    #[must_use]
+    pub fn was_initialized(&self) -> bool {
+        matches!(self, Self::Init(_))
+    }
+}
+impl<AI, AL, D> Debug for InitIfNeeded<AI, AL, D>
+where
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+    DiscriminantAccount<AI, AL, D>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InitIfNeeded::Init(init) => f.debug_tuple("Init").field(init).finish(),
+            InitIfNeeded::Existing(existing) => f.debug_tuple("Existing").field(existing).finish(),
+        }
+    }
+}
+impl<AI, AL, D> Deref for InitIfNeeded<AI, AL, D>
+where
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    type Target = DiscriminantAccount<AI, AL, D>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            InitIfNeeded::Init(init) => init,
+            InitIfNeeded::Existing(existing) => existing,
+        }
+    }
+}
+impl<AI, AL, D> DerefMut for InitIfNeeded<AI, AL, D>
+where
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            InitIfNeeded::Init(init) => init,
+            InitIfNeeded::Existing(existing) => existing,
+        }
+    }
+}
+impl<AI, AL, D> AccountArgument for InitIfNeeded<AI, AL, D>
+where
+    AI: AccountInfo,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        match self {
+            InitIfNeeded::Init(init) => init.write_back(program_id),
+            InitIfNeeded::Existing(existing) => existing.write_back(program_id),
+        }
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        match self {
+            InitIfNeeded::Init(init) => init.add_keys(add),
+            InitIfNeeded::Existing(existing) => existing.add_keys(add),
+        }
+    }
+}
+impl<AI, AL, D> FromAccounts<D> for InitIfNeeded<AI, AL, D>
+where
+    AI: AccountInfo,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: D,
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, ())?;
+        if &*info.owner() == program_id {
+            // Check the discriminant before deserializing so a different account type gives a useful error
+            assert_discriminant::<_, AL, D>(&info)?;
+            Ok(Self::Existing(DiscriminantAccount::from_accounts(
+                program_id,
+                &mut once(info),
+                (),
+            )?))
+        } else {
+            Ok(Self::Init(InitAccount::from_accounts(
+                program_id,
+                &mut once(info),
+                arg,
+            )?))
+        }
+    }
+
+    fn accounts_usage_hint(_arg: &D) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(&())
+    }
+}
+impl<'a, 'b, AI, AL, D, C> ValidateArgument<InitArgs<'a, AI, C>> for InitIfNeeded<AI, AL, D>
+where
+    AI: ToSolanaAccountInfo<'b>,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+    C: CPI + Copy,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: InitArgs<'a, AI, C>) -> CruiserResult<()> {
+        match self {
+            InitIfNeeded::Init(init) => init.validate(program_id, arg),
+            InitIfNeeded::Existing(existing) => {
+                existing.validate(program_id, ())?;
+                verify_existing_address(existing, program_id, arg.account_seeds)
+            }
+        }
+    }
+}
+impl<'a, 'b, AI, AL, D, C> ValidateArgument<StoreBump<'a, AI, C>> for InitIfNeeded<AI, AL, D>
+where
+    AI: ToSolanaAccountInfo<'b>,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize + StoredBumpSeed,
+    C: CPI + Copy,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: StoreBump<'a, AI, C>) -> CruiserResult<()> {
+        match self {
+            InitIfNeeded::Init(init) => init.validate(program_id, arg),
+            InitIfNeeded::Existing(existing) => {
+                existing.validate(program_id, ())?;
+                verify_existing_address(existing, program_id, arg.0.account_seeds)
+            }
+        }
+    }
+}
+/// Existing accounts were not created from `account_seeds` by this instruction so their address must be checked
+fn verify_existing_address<AI, AL, D>(
+    existing: &DiscriminantAccount<AI, AL, D>,
+    program_id: &Pubkey,
+    account_seeds: Option<&PDASeedSet>,
+) -> CruiserResult<()>
+where
+    AI: AccountInfo,
+    AL: AccountListItem<D>,
+{
+    match account_seeds {
+        Some(account_seeds) => account_seeds.verify_address(program_id, existing.info.key()),
+        None => Ok(()),
+    }
+}
+impl<AI, AL, D, T> MultiIndexable<T> for InitIfNeeded<AI, AL, D>
+where
+    AI: AccountInfo + MultiIndexable<T>,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    fn index_is_signer(&self, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, AL, D, T> SingleIndexable<T> for InitIfNeeded<AI, AL, D>
+where
+    AI: AccountInfo + SingleIndexable<T>,
+    AL: AccountListItem<D>,
+    D: BorshSerialize + BorshDeserialize,
+{
+    fn index_info(&self, indexer: T) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use solana_program::rent::Rent;
+
+    use crate::account_list::AccountList;
+    use crate::account_types::system_program::SystemProgram;
+    use crate::cpi::CPIChecked;
+    use crate::pda_seeds::PDASeeder;
+    use crate::traits::program::ProgramKey;
+    use crate::CruiserAccountInfo;
+
+    use super::*;
+
+    #[derive(AccountList)]
+    enum TestAccounts {
+        Data(TestData),
+    }
+    #[derive(Debug, Default, BorshSerialize, BorshDeserialize)]
+    struct TestData {
+        value: u64,
+    }
+
+    #[derive(Debug, PDASeeder)]
+    #[seeder(prefix = "test")]
+    struct TestSeeder {
+        key: Pubkey,
+    }
+
+    fn account_info(
+        key: Pubkey,
+        owner: Pubkey,
+        data: Vec<u8>,
+        is_signer: bool,
+        executable: bool,
+    ) -> CruiserAccountInfo {
+        let data = Box::leak(data.into_boxed_slice());
+        CruiserAccountInfo {
+            key: Box::leak(Box::new(key)),
+            is_signer,
+            is_writable: !executable,
+            lamports: Rc::new(RefCell::new(Box::leak(Box::new(1_000_000)))),
+            original_data_len: Box::leak(Box::new(data.len())),
+            data: Rc::new(RefCell::new(data)),
+            owner: Box::leak(Box::new(RefCell::new(Box::leak(Box::new(owner))))),
+            executable,
+            rent_epoch: 0,
+        }
+    }
+
+    fn validate_existing(at_seeds: bool) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let (seeds_key, account_seeds) = PDASeedSet::find(
+            TestSeeder {
+                key: Pubkey::new_unique(),
+            },
+            &program_id,
+        );
+        let key = if at_seeds {
+            seeds_key
+        } else {
+            Pubkey::new_unique()
+        };
+
+        let mut data = <TestAccounts as AccountListItem<TestData>>::DISCRIMINANT_BYTES.to_vec();
+        TestData { value: 10 }.serialize(&mut data).unwrap();
+        let info = account_info(key, program_id, data, false, false);
+        let mut account: InitIfNeeded<_, TestAccounts, TestData> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), TestData::default())?;
+        assert!(matches!(account, InitIfNeeded::Existing(_)));
+
+        let system_program = SystemProgram {
+            info: account_info(
+                SystemProgram::<()>::KEY,
+                Pubkey::new_unique(),
+                vec![],
+                false,
+                true,
+            ),
+        };
+        let funder = account_info(
+            Pubkey::new_unique(),
+            SystemProgram::<()>::KEY,
+            vec![],
+            true,
+            false,
+        );
+
+        account.validate(
+            &program_id,
+            InitArgs {
+                system_program: &system_program,
+                space: 16,
+                funder: &funder,
+                funder_seeds: None,
+                account_seeds: Some(&account_seeds),
+                rent: Some(Rent::default()),
+                cpi: CPIChecked,
+            },
+        )
+    }
+
+    #[test]
+    fn existing_address_test() {
+        validate_existing(true).unwrap();
+        assert!(validate_existing(false).is_err());
+    }
+}
@@ -31,8 +31,8 @@ use crate::{CruiserResult, ToSolanaAccountInfo};
 //                 D;
 //             ];
 //             validate: [
-//                 <'a, 'b, C> InitArgs<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI;
-//                 <'a, 'b, C> (InitArgs<'a, AI, C>, CheckAll) where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI;
+//                 <'a, 'b, C> InitArgs<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy;
+//                 <'a, 'b, C> (InitArgs<'a, AI, C>, CheckAll) where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy;
 //             ];
 //             multi: [(); AllAny];
 //             single: [()];
@@ -138,7 +138,7 @@ where
     AI: ToSolanaAccountInfo<'b>,
     AL: AccountListItem<D>,
     D: BorshSerialize + BorshDeserialize,
-    C: CPI,
+    C: CPI + Copy,
 {
     fn validate(&mut self, program_id: &Pubkey, arg: InitArgs<'a, AI, C>) -> CruiserResult<()> {
         match self {
@@ -153,7 +153,7 @@ where
     AI: ToSolanaAccountInfo<'b>,
     AL: AccountListItem<D>,
     D: BorshSerialize + BorshDeserialize,
-    C: CPI,
+    C: CPI + Copy,
 {
     fn validate(
         &mut self,
@@ -0,0 +1,391 @@
+//! Instruction introspection through the instructions sysvar
+
+use std::ops::Deref;
+
+use borsh::BorshDeserialize;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::instructions::{
+    self, get_instruction_relative, load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::account_argument::{AccountArgument, MultiIndexable, SingleIndexable, ValidateArgument};
+use crate::compressed_numbers::CompressedNumber;
+use crate::instruction_list::InstructionList;
+use crate::program::CruiserProgram;
+use crate::{
+    AccountInfo, CruiserResult, GenericError, SolanaAccountMeta, SolanaInstruction,
+    ToSolanaAccountInfo,
+};
+
+// verify_account_arg_impl! {
+//     mod instructions_sysvar_check<AI>{
+//         <AI> InstructionsSysvar<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [
+//                 ();
+//                 /// Checks that the current instruction was not invoked through CPI.
+//                 <'a> NoCPI where AI: ToSolanaAccountInfo<'a>;
+//             ];
+//             multi: [(); AllAny];
+//             single: [()];
+//         }
+//     }
+// }
+
+/// The instructions sysvar, allows introspection of the other instructions in the transaction.
+#[derive(AccountArgument, Debug)]
+#[account_argument(account_info = AI, generics = [where AI: AccountInfo])]
+pub struct InstructionsSysvar<AI>(#[validate(key = &instructions::ID)] pub AI);
+impl<AI> InstructionsSysvar<AI>
+where
+    AI: AccountInfo,
+{
+    /// The number of instructions in the transaction
+    pub fn num_instructions(&self) -> CruiserResult<u16> {
+        let data = self.0.data();
+        match data.get(..2) {
+            Some(&[first, second]) => Ok(u16::from_le_bytes([first, second])),
+            _ => Err(GenericError::NotEnoughData {
+                needed: 2,
+                remaining: data.len(),
+            }
+            .into()),
+        }
+    }
+}
+impl<'a, AI> InstructionsSysvar<AI>
+where
+    AI: ToSolanaAccountInfo<'a>,
+{
+    /// The index of the currently executing instruction in the transaction
+    pub fn current_index(&self) -> CruiserResult<u16> {
+        // Safety: the solana account info is dropped before returning
+        unsafe {
+            Ok(load_current_index_checked(
+                &self.0.to_solana_account_info(),
+            )?)
+        }
+    }
+
+    /// Loads the instruction at an absolute index in the transaction
+    pub fn instruction_at(&self, index: usize) -> CruiserResult<SolanaInstruction> {
+        // Safety: the solana account info is dropped before returning
+        unsafe {
+            Ok(load_instruction_at_checked(
+                index,
+                &self.0.to_solana_account_info(),
+            )?)
+        }
+    }
+
+    /// Loads the instruction at an index relative to the currently executing instruction.
+    /// `-1` is the previous instruction, `1` is the next.
+    pub fn instruction_relative(&self, offset: i64) -> CruiserResult<SolanaInstruction> {
+        // Safety: the solana account info is dropped before returning
+        unsafe {
+            Ok(get_instruction_relative(
+                offset,
+                &self.0.to_solana_account_info(),
+            )?)
+        }
+    }
+
+    /// Loads the instruction at an absolute index and decodes it if it targets the cruiser program `P`.
+    /// Returns [`None`] if the instruction is for a different program.
+    pub fn cruiser_instruction_at<P>(
+        &self,
+        index: usize,
+    ) -> CruiserResult<Option<DecodedInstruction<P::InstructionList>>>
+    where
+        P: CruiserProgram,
+    {
+        DecodedInstruction::decode(self.instruction_at(index)?, &P::KEY)
+    }
+
+    /// Loads the instruction at an index relative to the current one and decodes it if it targets the cruiser program `P`.
+    /// Returns [`None`] if the instruction is for a different program.
+    pub fn cruiser_instruction_relative<P>(
+        &self,
+        offset: i64,
+    ) -> CruiserResult<Option<DecodedInstruction<P::InstructionList>>>
+    where
+        P: CruiserProgram,
+    {
+        DecodedInstruction::decode(self.instruction_relative(offset)?, &P::KEY)
+    }
+
+    /// Returns true if the currently executing instruction is a top level instruction of `program_id` rather than a CPI.
+    ///
+    /// Only compares the top level instruction's program id so a CPI from `program_id` into itself is not detected.
+    /// Solana versions supported by this crate do not expose the invoke stack height needed to detect it.
+    pub fn is_top_level(&self, program_id: &Pubkey) -> CruiserResult<bool> {
+        Ok(&self.instruction_relative(0)?.program_id == program_id)
+    }
+}
+impl<AI> Deref for InstructionsSysvar<AI>
+where
+    AI: AccountInfo,
+{
+    type Target = AI;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+/// Validates that the current instruction is a top level instruction of the current program and was not invoked through CPI.
+/// Does not detect the current program invoking itself, see [`InstructionsSysvar::is_top_level`].
+#[derive(Debug, Copy, Clone)]
+pub struct NoCPI;
+impl<'a, AI> ValidateArgument<NoCPI> for InstructionsSysvar<AI>
+where
+    AI: ToSolanaAccountInfo<'a>,
+{
+    fn validate(&mut self, program_id: &Pubkey, _arg: NoCPI) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        if self.is_top_level(program_id)? {
+            Ok(())
+        } else {
+            Err(GenericError::CalledThroughCPI {
+                program_id: *program_id,
+            }
+            .into())
+        }
+    }
+}
+impl<AI, T> MultiIndexable<T> for InstructionsSysvar<AI>
+where
+    AI: AccountInfo + MultiIndexable<T>,
+{
+    fn index_is_signer(&self, indexer: T) -> CruiserResult<bool> {
+        self.0.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: T) -> CruiserResult<bool> {
+        self.0.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: T) -> CruiserResult<bool> {
+        self.0.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, T> SingleIndexable<T> for InstructionsSysvar<AI>
+where
+    AI: AccountInfo + SingleIndexable<T>,
+{
+    fn index_info(&self, indexer: T) -> CruiserResult<&AI> {
+        self.0.index_info(indexer)
+    }
+}
+
+/// An instruction of a cruiser program decoded from the instructions sysvar
+#[derive(Debug, Clone)]
+pub struct DecodedInstruction<IL> {
+    /// The instruction from the program's instruction list
+    pub instruction: IL,
+    /// The accounts passed to the instruction
+    pub accounts: Vec<SolanaAccountMeta>,
+    /// The instruction data after the discriminant
+    pub data: Vec<u8>,
+}
+impl<IL> DecodedInstruction<IL>
+where
+    IL: InstructionList,
+{
+    /// Decodes an instruction for `program_id`, returns [`None`] if the instruction is for a different program.
+    pub fn decode(
+        instruction: SolanaInstruction,
+        program_id: &Pubkey,
+    ) -> CruiserResult<Option<Self>> {
+        if &instruction.program_id != program_id {
+            return Ok(None);
+        }
+        let mut data = instruction.data.as_slice();
+        let discriminant = IL::DiscriminantCompressed::deserialize(&mut data)?.into_number();
+        let list_item = IL::from_discriminant(discriminant).ok_or_else(|| {
+            GenericError::UnknownInstruction {
+                instruction: discriminant.to_string(),
+            }
+        })?;
+        let data = data.to_vec();
+        Ok(Some(Self {
+            instruction: list_item,
+            accounts: instruction.accounts,
+            data,
+        }))
+    }
+
+    /// Deserializes the instruction data
+    pub fn data<D>(&self) -> CruiserResult<D>
+    where
+        D: BorshDeserialize,
+    {
+        Ok(D::deserialize(&mut self.data.as_slice())?)
+    }
+
+    /// Gets the account at `index`
+    pub fn account(&self, index: usize) -> CruiserResult<&SolanaAccountMeta> {
+        self.accounts.get(index).ok_or_else(|| {
+            GenericError::IndexOutOfRange {
+                index: index.to_string(),
+                possible_range: format!("[0, {})", self.accounts.len()),
+            }
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::num::NonZeroU64;
+    use std::rc::Rc;
+
+    use borsh::BorshSerialize;
+
+    use super::*;
+    use crate::account_list::AccountList;
+    use crate::compressed_numbers::Leb128;
+    use crate::CruiserAccountInfo;
+
+    struct TestAccounts;
+    impl AccountList for TestAccounts {
+        type DiscriminantCompressed = Leb128<NonZeroU64>;
+    }
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    enum TestInstructions {
+        First,
+        Second,
+    }
+    impl InstructionList for TestInstructions {
+        type DiscriminantCompressed = Leb128<u64>;
+        type AccountList = TestAccounts;
+
+        fn discriminant(self) -> u64 {
+            self as u64
+        }
+
+        fn discriminant_bytes(self) -> &'static [u8] {
+            match self {
+                Self::First => &[0],
+                Self::Second => &[1],
+            }
+        }
+
+        fn from_discriminant(discriminant: u64) -> Option<Self> {
+            match discriminant {
+                0 => Some(Self::First),
+                1 => Some(Self::Second),
+                _ => None,
+            }
+        }
+    }
+
+    /// Serializes `instructions` the same way the runtime fills the instructions sysvar
+    fn sysvar_data(instructions: &[SolanaInstruction], current_index: u16) -> Vec<u8> {
+        let mut data = (instructions.len() as u16).to_le_bytes().to_vec();
+        let offsets_start = data.len();
+        data.resize(offsets_start + instructions.len() * 2, 0);
+        for (index, instruction) in instructions.iter().enumerate() {
+            let offset = (data.len() as u16).to_le_bytes();
+            data[offsets_start + index * 2..][..2].copy_from_slice(&offset);
+            data.extend_from_slice(&(instruction.accounts.len() as u16).to_le_bytes());
+            for meta in &instruction.accounts {
+                data.push(u8::from(meta.is_signer) | u8::from(meta.is_writable) << 1);
+                data.extend_from_slice(meta.pubkey.as_ref());
+            }
+            data.extend_from_slice(instruction.program_id.as_ref());
+            data.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+            data.extend_from_slice(&instruction.data);
+        }
+        data.extend_from_slice(&current_index.to_le_bytes());
+        data
+    }
+
+    fn sysvar_account(key: Pubkey, data: Vec<u8>) -> InstructionsSysvar<CruiserAccountInfo> {
+        let data = Box::leak(data.into_boxed_slice());
+        InstructionsSysvar(CruiserAccountInfo {
+            key: Box::leak(Box::new(key)),
+            is_signer: false,
+            is_writable: false,
+            lamports: Rc::new(RefCell::new(Box::leak(Box::new(0)))),
+            original_data_len: Box::leak(Box::new(data.len())),
+            data: Rc::new(RefCell::new(data)),
+            owner: Box::leak(Box::new(RefCell::new(Box::leak(Box::new(
+                Pubkey::new_unique(),
+            ))))),
+            executable: false,
+            rent_epoch: 0,
+        })
+    }
+
+    fn instructions(program_id: Pubkey, account: Pubkey) -> Vec<SolanaInstruction> {
+        let mut data = TestInstructions::Second.discriminant_bytes().to_vec();
+        7u32.serialize(&mut data).unwrap();
+        vec![
+            SolanaInstruction {
+                program_id: Pubkey::new_unique(),
+                accounts: vec![],
+                data: vec![1, 2, 3],
+            },
+            SolanaInstruction {
+                program_id,
+                accounts: vec![SolanaAccountMeta::new(account, true)],
+                data,
+            },
+            SolanaInstruction {
+                program_id,
+                accounts: vec![],
+                data: vec![5],
+            },
+        ]
+    }
+
+    #[test]
+    fn decoded_instruction_test() -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let sysvar = sysvar_account(
+            instructions::ID,
+            sysvar_data(&instructions(program_id, account), 1),
+        );
+        assert_eq!(sysvar.num_instructions()?, 3);
+        assert_eq!(sysvar.current_index()?, 1);
+
+        assert!(DecodedInstruction::<TestInstructions>::decode(
+            sysvar.instruction_at(0)?,
+            &program_id
+        )?
+        .is_none());
+        let decoded =
+            DecodedInstruction::<TestInstructions>::decode(sysvar.instruction_at(1)?, &program_id)?
+                .unwrap();
+        assert_eq!(decoded.instruction, TestInstructions::Second);
+        assert_eq!(decoded.data::<u32>()?, 7);
+        assert_eq!(decoded.account(0)?, &SolanaAccountMeta::new(account, true));
+        assert!(decoded.account(1).is_err());
+        assert!(DecodedInstruction::<TestInstructions>::decode(
+            sysvar.instruction_relative(1)?,
+            &program_id
+        )
+        .is_err());
+        Ok(())
+    }
+
+    fn validate_no_cpi(key: Pubkey, current_index: u16) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let data = sysvar_data(
+            &instructions(program_id, Pubkey::new_unique()),
+            current_index,
+        );
+        sysvar_account(key, data).validate(&program_id, NoCPI)
+    }
+
+    #[test]
+    fn no_cpi_test() {
+        validate_no_cpi(instructions::ID, 1).unwrap();
+        validate_no_cpi(instructions::ID, 2).unwrap();
+        assert!(validate_no_cpi(instructions::ID, 0).is_err());
+        assert!(validate_no_cpi(Pubkey::new_unique(), 1).is_err());
+    }
+}
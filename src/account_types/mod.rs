@@ -1,16 +1,27 @@
 //! Standard account types. These are all optional, you can build your own if you don't like something in one of them.
 
+pub mod any_account;
 pub mod close_account;
 pub mod cruiser_program_account;
 pub mod data_account;
 pub mod discriminant_account;
 pub mod init_account;
+pub mod init_if_needed;
 pub mod init_or_zeroed_account;
+pub mod instructions_sysvar;
+pub mod owned;
+pub mod packed_account;
+pub mod program_account;
+pub mod program_data_account;
 pub mod rent_exempt;
 pub mod rest;
 pub mod seeds;
+pub mod signature_verification;
+pub mod signer;
 pub mod sys_var;
 pub mod system_program;
+pub mod versioned_account;
+pub mod writable;
 pub mod zeroed_account;
 
 use crate::account_argument::{
@@ -0,0 +1,191 @@
+//! A wrapper that requires its account to be owned by a given program
+
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::program::ProgramKey;
+use crate::util::assert::assert_is_owner;
+use crate::{AccountInfo, CruiserResult};
+
+// verify_account_arg_impl! {
+//     mod owned_check<AI>{
+//         <T, P> Owned<T, P> where T: AccountArgument<AI>, P: ProgramKey{
+//             from: [
+//                 <Arg> Arg where T: FromAccounts<Arg>;
+//             ];
+//             validate: [
+//                 /// Requires all accounts to be owned by `P`.
+//                 () where T::AccountInfo: AccountInfo, T: ValidateArgument<()> + MultiIndexable<()>;
+//                 /// Requires all accounts to be owned by `P`.
+//                 <Arg> (Arg,) where T::AccountInfo: AccountInfo, T: ValidateArgument<Arg> + MultiIndexable<()>;
+//                 /// Requires the accounts at the indexer to be owned by `P`.
+//                 <Arg, I> (Arg, I) where T::AccountInfo: AccountInfo, T: ValidateArgument<Arg> + MultiIndexable<I>, I: Debug + Clone;
+//             ];
+//             multi: [<I> I where T: MultiIndexable<I>];
+//             single: [<I> I where T: SingleIndexable<I>];
+//         }
+//     }
+// }
+
+/// A wrapper that ensures the wrapped argument is owned by the program `P`.
+/// Functions can take `&Owned<AI, P>` to require that the owner check was done.
+pub struct Owned<T, P>(T, PhantomData<fn() -> P>);
+impl<T, P> Owned<T, P> {
+    /// Unwraps the inner argument
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T, P> Debug for Owned<T, P>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Owned").field(&self.0).finish()
+    }
+}
+impl<T, P> Deref for Owned<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<T, P> DerefMut for Owned<T, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<T, P> AccountArgument for Owned<T, P>
+where
+    T: AccountArgument,
+{
+    type AccountInfo = T::AccountInfo;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.0.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.0.add_keys(add)
+    }
+}
+impl<T, P, Arg> FromAccounts<Arg> for Owned<T, P>
+where
+    T: FromAccounts<Arg>,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = T::AccountInfo>,
+        arg: Arg,
+    ) -> CruiserResult<Self> {
+        Ok(Self(T::from_accounts(program_id, infos, arg)?, PhantomData))
+    }
+
+    fn accounts_usage_hint(arg: &Arg) -> (usize, Option<usize>) {
+        T::accounts_usage_hint(arg)
+    }
+}
+impl<T, P> ValidateArgument<()> for Owned<T, P>
+where
+    P: ProgramKey,
+    T::AccountInfo: AccountInfo,
+    T: ValidateArgument<()> + MultiIndexable<()>,
+{
+    fn validate(&mut self, program_id: &Pubkey, _arg: ()) -> CruiserResult<()> {
+        self.validate(program_id, ((), ()))
+    }
+}
+impl<T, P, Arg> ValidateArgument<(Arg,)> for Owned<T, P>
+where
+    P: ProgramKey,
+    T::AccountInfo: AccountInfo,
+    T: ValidateArgument<Arg> + MultiIndexable<()>,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: (Arg,)) -> CruiserResult<()> {
+        self.validate(program_id, (arg.0, ()))
+    }
+}
+impl<T, P, Arg, I> ValidateArgument<(Arg, I)> for Owned<T, P>
+where
+    P: ProgramKey,
+    T::AccountInfo: AccountInfo,
+    T: ValidateArgument<Arg> + MultiIndexable<I>,
+    I: Debug + Clone,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: (Arg, I)) -> CruiserResult<()> {
+        self.0.validate(program_id, arg.0)?;
+        assert_is_owner(&self.0, &P::KEY, arg.1)
+    }
+}
+impl<T, P, Arg> MultiIndexable<Arg> for Owned<T, P>
+where
+    T: MultiIndexable<Arg>,
+{
+    #[inline]
+    fn index_is_signer(&self, indexer: Arg) -> CruiserResult<bool> {
+        self.0.index_is_signer(indexer)
+    }
+
+    #[inline]
+    fn index_is_writable(&self, indexer: Arg) -> CruiserResult<bool> {
+        self.0.index_is_writable(indexer)
+    }
+
+    #[inline]
+    fn index_is_owner(&self, owner: &Pubkey, indexer: Arg) -> CruiserResult<bool> {
+        self.0.index_is_owner(owner, indexer)
+    }
+}
+impl<T, P, Arg> SingleIndexable<Arg> for Owned<T, P>
+where
+    T: SingleIndexable<Arg>,
+{
+    #[inline]
+    fn index_info(&self, indexer: Arg) -> CruiserResult<&Self::AccountInfo> {
+        self.0.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account_types::system_program::SystemProgram;
+    use crate::SolanaAccountInfo;
+
+    fn validate(owner: Pubkey) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![];
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let mut account: Owned<SolanaAccountInfo, SystemProgram<()>> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+        account.validate(&program_id, ())?;
+        assert_eq!(account.into_inner().key, &key);
+        Ok(())
+    }
+
+    #[test]
+    fn owned_test() {
+        validate(SystemProgram::<()>::KEY).unwrap();
+        assert!(validate(Pubkey::new_unique()).is_err());
+    }
+}
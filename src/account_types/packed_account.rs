@@ -0,0 +1,162 @@
+//! An account owned by a given program whose data is a [`Pack`] type
+
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use solana_program::program_pack::{IsInitialized, Pack};
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::on_chain_size::OnChainSize;
+use crate::program::ProgramKey;
+use crate::{AccountInfo, CruiserResult, GenericError};
+
+// verify_account_arg_impl! {
+//     mod packed_account_check<AI>{
+//         <AI, T, P> PackedAccount<AI, T, P> where AI: AccountInfo, T: Pack + IsInitialized, P: ProgramKey{
+//             from: [()];
+//             validate: [()];
+//             multi: [<I> I where AI: MultiIndexable<AI, I>];
+//             single: [<I> I where AI: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// An account owned by the program `P` with data of [`Pack`] type `T`.
+/// Useful for reading state of programs not written with cruiser such as the SPL programs.
+///
+/// The data is only written back if `P` is the current program and the account is writable.
+pub struct PackedAccount<AI, T, P> {
+    /// The [`AccountInfo`] of this account.
+    pub info: AI,
+    data: T,
+    phantom_p: PhantomData<fn() -> P>,
+}
+impl<AI, T, P> Debug for PackedAccount<AI, T, P>
+where
+    AI: Debug,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PackedAccount")
+            .field("info", &self.info)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+impl<AI, T, P> OnChainSize<()> for PackedAccount<AI, T, P>
+where
+    T: Pack,
+{
+    fn on_chain_max_size(_arg: ()) -> usize {
+        T::LEN
+    }
+}
+impl<AI, T, P> Deref for PackedAccount<AI, T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+impl<AI, T, P> DerefMut for PackedAccount<AI, T, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+impl<AI, T, P> AccountArgument for PackedAccount<AI, T, P>
+where
+    AI: AccountInfo,
+    T: Pack + IsInitialized,
+    P: ProgramKey,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        if program_id == &P::KEY && self.info.is_writable() {
+            T::pack(self.data, &mut self.info.data_mut())?;
+        }
+        self.info.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.info.add_keys(add)
+    }
+}
+impl<AI, T, P> FromAccounts<()> for PackedAccount<AI, T, P>
+where
+    AI: AccountInfo,
+    T: Pack + IsInitialized,
+    P: ProgramKey,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, arg)?;
+        let data = T::unpack(&info.data())?;
+        Ok(Self {
+            info,
+            data,
+            phantom_p: PhantomData,
+        })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<AI, T, P> ValidateArgument<()> for PackedAccount<AI, T, P>
+where
+    AI: AccountInfo,
+    T: Pack + IsInitialized,
+    P: ProgramKey,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.info.validate(program_id, arg)?;
+        let owner = *self.info.owner();
+        if owner == P::KEY {
+            Ok(())
+        } else {
+            Err(GenericError::AccountOwnerNotEqual {
+                account: *self.info.key(),
+                owner,
+                expected_owner: vec![P::KEY],
+            }
+            .into())
+        }
+    }
+}
+impl<AI, T, P, I> MultiIndexable<I> for PackedAccount<AI, T, P>
+where
+    AI: AccountInfo + MultiIndexable<I>,
+    T: Pack + IsInitialized,
+    P: ProgramKey,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, T, P, I> SingleIndexable<I> for PackedAccount<AI, T, P>
+where
+    AI: AccountInfo + SingleIndexable<I>,
+    T: Pack + IsInitialized,
+    P: ProgramKey,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
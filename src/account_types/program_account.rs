@@ -0,0 +1,230 @@
+//! An account that is any executable program with a known key
+
+use std::fmt::{Debug, Formatter};
+use std::iter::once;
+use std::marker::PhantomData;
+
+use solana_program::entrypoint::ProgramResult;
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::pda_seeds::PDASeedSet;
+use crate::program::{Program, ProgramKey};
+use crate::util::assert::assert_is_key;
+use crate::{
+    AccountInfo, CruiserResult, GenericError, SolanaInstruction, ToSolanaAccountInfo, CPI,
+};
+
+// verify_account_arg_impl! {
+//     mod program_account_check<AI>{
+//         <AI, P> ProgramAccount<AI, P> where AI: AccountInfo, P: ProgramKey{
+//             from: [()];
+//             validate: [()];
+//             multi: [<I> I where AI: MultiIndexable<AI, I>];
+//             single: [<I> I where AI: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// An executable program with key `P::KEY`, for programs not written with cruiser.
+/// `P` can be declared with [`declare_program!`](crate::declare_program).
+pub struct ProgramAccount<AI, P> {
+    /// The [`AccountInfo`] of this program.
+    pub info: AI,
+    phantom_p: PhantomData<fn() -> P>,
+}
+impl<'a, AI, P> ProgramAccount<AI, P>
+where
+    AI: ToSolanaAccountInfo<'a>,
+    P: ProgramKey,
+{
+    /// Invokes an instruction of this program. This program's account is added to `account_infos`.
+    pub fn invoke<'b>(
+        &'b self,
+        cpi: impl CPI,
+        instruction: &SolanaInstruction,
+        account_infos: impl IntoIterator<Item = &'b AI>,
+    ) -> ProgramResult {
+        cpi.invoke_variable_size(
+            instruction,
+            account_infos.into_iter().chain(once(&self.info)),
+        )
+    }
+
+    /// Invokes an instruction of this program signing with `seeds`. This program's account is added to `account_infos`.
+    pub fn invoke_signed<'b, 'c: 'b>(
+        &'b self,
+        cpi: impl CPI,
+        instruction: &SolanaInstruction,
+        account_infos: impl IntoIterator<Item = &'b AI>,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_variable_size_multiple(
+            cpi,
+            instruction,
+            account_infos.into_iter().chain(once(&self.info)),
+            seeds,
+        )
+    }
+}
+impl<AI, P> Debug for ProgramAccount<AI, P>
+where
+    AI: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgramAccount")
+            .field("info", &self.info)
+            .finish()
+    }
+}
+impl<AI, P> Clone for ProgramAccount<AI, P>
+where
+    AI: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            info: self.info.clone(),
+            phantom_p: PhantomData,
+        }
+    }
+}
+impl<AI, P> ProgramKey for ProgramAccount<AI, P>
+where
+    P: ProgramKey,
+{
+    const KEY: Pubkey = P::KEY;
+}
+impl<AI, P> Program for ProgramAccount<AI, P>
+where
+    AI: AccountInfo,
+    P: ProgramKey,
+{
+}
+impl<AI, P> AccountArgument for ProgramAccount<AI, P>
+where
+    AI: AccountInfo,
+    P: ProgramKey,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.info.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.info.add_keys(add)
+    }
+}
+impl<AI, P> FromAccounts<()> for ProgramAccount<AI, P>
+where
+    AI: AccountInfo,
+    P: ProgramKey,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        Ok(Self {
+            info: AI::from_accounts(program_id, infos, arg)?,
+            phantom_p: PhantomData,
+        })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<AI, P> ValidateArgument<()> for ProgramAccount<AI, P>
+where
+    AI: AccountInfo,
+    P: ProgramKey,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.info.validate(program_id, arg)?;
+        assert_is_key(&self.info, &P::KEY, ())?;
+        if self.info.executable() {
+            Ok(())
+        } else {
+            Err(GenericError::AccountNotExecutable {
+                account: *self.info.key(),
+            }
+            .into())
+        }
+    }
+}
+impl<AI, P, I> MultiIndexable<I> for ProgramAccount<AI, P>
+where
+    AI: AccountInfo + MultiIndexable<I>,
+    P: ProgramKey,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, P, I> SingleIndexable<I> for ProgramAccount<AI, P>
+where
+    AI: AccountInfo + SingleIndexable<I>,
+    P: ProgramKey,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use solana_program::pubkey;
+
+    use super::*;
+    use crate::SolanaAccountInfo;
+
+    declare_program!(
+        /// The SPL memo program
+        MemoProgram = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr")
+    );
+
+    fn validate(key: Pubkey, executable: bool) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![];
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            executable,
+            0,
+        );
+        let mut account: ProgramAccount<SolanaAccountInfo, MemoProgram> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+        account.validate(&program_id, ())?;
+        assert_eq!(account.info.key, &key);
+        Ok(())
+    }
+
+    #[test]
+    fn program_account_test() {
+        assert_eq!(
+            ProgramAccount::<(), MemoProgram>::KEY,
+            pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr")
+        );
+        validate(MemoProgram::KEY, true).unwrap();
+        assert!(validate(Pubkey::new_unique(), true).is_err());
+        assert!(validate(MemoProgram::KEY, false).is_err());
+    }
+}
@@ -0,0 +1,296 @@
+//! Support for the program data accounts of the upgradeable bpf loader
+
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::util::assert::assert_is_owner;
+use crate::{AccountInfo, CruiserResult, GenericError};
+
+// verify_account_arg_impl! {
+//     mod program_data_account_check<AI>{
+//         <AI> ProgramDataAccount<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [
+//                 /// Checks the owner is the upgradeable loader.
+//                 ();
+//                 /// Checks the account is the program data for the current program.
+//                 CurrentProgram;
+//                 /// Checks the account is the program data for the given program.
+//                 <'a> ProgramDataFor<'a>;
+//                 /// Checks the account is the program data for the current program and the given key is the upgrade authority.
+//                 <'a> UpgradeAuthority<'a>;
+//             ];
+//             multi: [<I> I where AI: MultiIndexable<I>];
+//             single: [<I> I where AI: SingleIndexable<I>];
+//         }
+//     }
+// }
+
+/// The program data account of a program deployed with the upgradeable bpf loader.
+/// Holds the slot the program was last deployed at and its upgrade authority.
+#[derive(Debug)]
+pub struct ProgramDataAccount<AI> {
+    /// The account associated
+    pub info: AI,
+    slot: u64,
+    upgrade_authority: Option<Pubkey>,
+}
+impl<AI> ProgramDataAccount<AI> {
+    /// Gets the program data address for a given program
+    #[must_use]
+    pub fn program_data_address(program: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[program.as_ref()], &bpf_loader_upgradeable::ID).0
+    }
+
+    /// The slot the program was last deployed at
+    #[must_use]
+    pub fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    /// The upgrade authority of the program, [`None`] if the program is immutable
+    #[must_use]
+    pub fn upgrade_authority(&self) -> Option<&Pubkey> {
+        self.upgrade_authority.as_ref()
+    }
+}
+impl<AI> ProgramDataAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn verify_program(&self, program: &Pubkey) -> CruiserResult<()> {
+        let expected = Self::program_data_address(program);
+        if self.info.key() == &expected {
+            Ok(())
+        } else {
+            Err(GenericError::InvalidAccount {
+                account: *self.info.key(),
+                expected,
+            }
+            .into())
+        }
+    }
+}
+impl<AI> AccountArgument for ProgramDataAccount<AI>
+where
+    AI: AccountInfo,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.info.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.info.add_keys(add)
+    }
+}
+impl<AI> FromAccounts<()> for ProgramDataAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, arg)?;
+        let state = bincode::deserialize::<UpgradeableLoaderState>(&info.data());
+        match state {
+            Ok(UpgradeableLoaderState::ProgramData {
+                slot,
+                upgrade_authority_address,
+            }) => Ok(Self {
+                info,
+                slot,
+                upgrade_authority: upgrade_authority_address,
+            }),
+            _ => Err(GenericError::CouldNotDeserialize {
+                what: format!("`{}` as program data", info.key()),
+            }
+            .into()),
+        }
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<AI> ValidateArgument<()> for ProgramDataAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.info.validate(program_id, arg)?;
+        assert_is_owner(&self.info, &bpf_loader_upgradeable::ID, ())
+    }
+}
+/// Validates that the [`ProgramDataAccount`] belongs to the current program
+#[derive(Debug, Copy, Clone)]
+pub struct CurrentProgram;
+impl<AI> ValidateArgument<CurrentProgram> for ProgramDataAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, _arg: CurrentProgram) -> CruiserResult<()> {
+        self.validate(program_id, ProgramDataFor(program_id))
+    }
+}
+/// Validates that the [`ProgramDataAccount`] belongs to the given program
+#[derive(Debug, Copy, Clone)]
+pub struct ProgramDataFor<'a>(pub &'a Pubkey);
+impl<AI> ValidateArgument<ProgramDataFor<'_>> for ProgramDataAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ProgramDataFor) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        self.verify_program(arg.0)
+    }
+}
+/// Validates that the [`ProgramDataAccount`] belongs to the current program and that the given key is its upgrade authority.
+/// Combine with `#[validate(signer)]` on the authority to require its signature.
+#[derive(Debug, Copy, Clone)]
+pub struct UpgradeAuthority<'a>(pub &'a Pubkey);
+impl<AI> ValidateArgument<UpgradeAuthority<'_>> for ProgramDataAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: UpgradeAuthority) -> CruiserResult<()> {
+        self.validate(program_id, CurrentProgram)?;
+        match &self.upgrade_authority {
+            Some(authority) if authority == arg.0 => Ok(()),
+            Some(authority) => Err(GenericError::InvalidAccount {
+                account: *arg.0,
+                expected: *authority,
+            }
+            .into()),
+            None => Err(GenericError::ProgramNotUpgradeable {
+                program_data: *self.info.key(),
+            }
+            .into()),
+        }
+    }
+}
+impl<AI, I> MultiIndexable<I> for ProgramDataAccount<AI>
+where
+    AI: AccountInfo + MultiIndexable<I>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, I> SingleIndexable<I> for ProgramDataAccount<AI>
+where
+    AI: AccountInfo + SingleIndexable<I>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SolanaAccountInfo;
+
+    fn validate(
+        program_id: &Pubkey,
+        key: Pubkey,
+        upgrade_authority: Option<Pubkey>,
+        validate: impl FnOnce(&mut ProgramDataAccount<SolanaAccountInfo>) -> CruiserResult<()>,
+    ) -> CruiserResult<()> {
+        let owner = bpf_loader_upgradeable::ID;
+        let mut lamports = 0;
+        let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 10,
+            upgrade_authority_address: upgrade_authority,
+        })
+        .unwrap();
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let mut account: ProgramDataAccount<SolanaAccountInfo> =
+            FromAccounts::from_accounts(program_id, &mut [info].into_iter(), ())?;
+        validate(&mut account)?;
+        assert_eq!(account.slot(), 10);
+        assert_eq!(account.upgrade_authority(), upgrade_authority.as_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn program_data_for_test() {
+        let program_id = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let key = ProgramDataAccount::<()>::program_data_address(&other_program);
+        validate(&program_id, key, None, |account| {
+            account.validate(&program_id, ProgramDataFor(&other_program))
+        })
+        .unwrap();
+        assert!(validate(&program_id, key, None, |account| {
+            account.validate(&program_id, ProgramDataFor(&program_id))
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn current_program_test() {
+        let program_id = Pubkey::new_unique();
+        let key = ProgramDataAccount::<()>::program_data_address(&program_id);
+        validate(&program_id, key, None, |account| {
+            account.validate(&program_id, CurrentProgram)
+        })
+        .unwrap();
+        assert!(
+            validate(&program_id, Pubkey::new_unique(), None, |account| {
+                account.validate(&program_id, CurrentProgram)
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn upgrade_authority_test() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let key = ProgramDataAccount::<()>::program_data_address(&program_id);
+        validate(&program_id, key, Some(authority), |account| {
+            account.validate(&program_id, UpgradeAuthority(&authority))
+        })
+        .unwrap();
+        assert!(validate(&program_id, key, Some(authority), |account| {
+            account.validate(&program_id, UpgradeAuthority(&Pubkey::new_unique()))
+        })
+        .is_err());
+        assert!(validate(&program_id, key, None, |account| {
+            account.validate(&program_id, UpgradeAuthority(&authority))
+        })
+        .is_err());
+        assert!(validate(
+            &program_id,
+            Pubkey::new_unique(),
+            Some(authority),
+            |account| { account.validate(&program_id, UpgradeAuthority(&authority)) }
+        )
+        .is_err());
+    }
+}
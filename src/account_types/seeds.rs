@@ -4,8 +4,13 @@ use crate::account_argument::{
     AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
     ValidateArgument,
 };
+use crate::account_list::AccountListItem;
+use crate::account_types::data_account::DataAccount;
+use crate::account_types::discriminant_account::DiscriminantAccount;
 use crate::pda_seeds::{PDAGenerator, PDASeedSet, PDASeeder};
+use crate::program::ProgramKey;
 use crate::{AccountInfo, AccountInfoAccess, CruiserResult};
+use borsh::BorshDeserialize;
 use solana_program::pubkey::Pubkey;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
@@ -15,9 +20,9 @@ use std::ops::{Deref, DerefMut};
 //         <T, S> Seeds<T, S> where AI: AccountInfo, T: AccountArgument<AI>, S: PDASeeder{
 //             from: [<Arg> Arg where T: FromAccounts<Arg>];
 //             validate: [
-//                 <B> (S, B) where T: ValidateArgument<()> + SingleIndexable<()>, B: BumpSeed;
-//                 <B, V> (S, B, V) where T: ValidateArgument<V> + SingleIndexable<()>, B: BumpSeed;
-//                 <B, V, I> (S, B, V, I) where T: ValidateArgument<V> + SingleIndexable<I>, B: BumpSeed;
+//                 <B> (S, B) where T: ValidateArgument<()> + SingleIndexable<()>, B: BumpSeed<T>;
+//                 <B, V> (S, B, V) where T: ValidateArgument<V> + SingleIndexable<()>, B: BumpSeed<T>;
+//                 <B, V, I> (S, B, V, I) where T: ValidateArgument<V> + SingleIndexable<I>, B: BumpSeed<T>;
 //             ];
 //             multi: [<Arg> Arg where T: MultiIndexable<Arg>];
 //             single: [<Arg> Arg where T: SingleIndexable<Arg>];
@@ -26,6 +31,7 @@ use std::ops::{Deref, DerefMut};
 // }
 
 /// Requires that the address comes from a given seeder. Can use a given bump seed or find the bump seed.
+/// Addresses are derived from the current program unless the bump seed is wrapped in [`OfProgram`].
 #[derive(Debug)]
 pub struct Seeds<T, S>
 where
@@ -45,6 +51,13 @@ where
         let seeds = self.seeds.take()?;
         Some(PDASeedSet::new(seeds.0, seeds.1))
     }
+
+    /// Gets the seed set borrowing the seeder, does not allocate.
+    /// Will be [`None`] if [`validate`](ValidateArgument::validate) was never called or [`Seeds::take_seed_set`] was called.
+    pub fn seed_set(&self) -> Option<PDASeedSet<'_>> {
+        let (seeder, nonce) = self.seeds.as_ref()?;
+        Some(PDASeedSet::borrowed(seeder, *nonce))
+    }
 }
 impl<T, S> Deref for Seeds<T, S>
 where
@@ -106,7 +119,7 @@ where
     T::AccountInfo: AccountInfo,
     T: ValidateArgument<()> + SingleIndexable<()>,
     S: PDASeeder,
-    B: BumpSeed,
+    B: BumpSeed<T>,
 {
     fn validate(&mut self, program_id: &Pubkey, arg: (S, B)) -> CruiserResult<()> {
         self.validate(program_id, (arg.0, arg.1, (), ()))
@@ -117,7 +130,7 @@ where
     T::AccountInfo: AccountInfo,
     T: ValidateArgument<V> + SingleIndexable<()>,
     S: PDASeeder,
-    B: BumpSeed,
+    B: BumpSeed<T>,
 {
     fn validate(&mut self, program_id: &Pubkey, arg: (S, B, V)) -> CruiserResult<()> {
         self.validate(program_id, (arg.0, arg.1, arg.2, ()))
@@ -128,13 +141,16 @@ where
     T::AccountInfo: AccountInfo,
     T: ValidateArgument<V> + SingleIndexable<I>,
     S: PDASeeder,
-    B: BumpSeed,
+    B: BumpSeed<T>,
 {
     fn validate(&mut self, program_id: &Pubkey, arg: (S, B, V, I)) -> CruiserResult<()> {
         self.argument.validate(program_id, arg.2)?;
-        let bump_seed = arg
-            .1
-            .verify_address(&arg.0, program_id, self.index_info(arg.3)?.key())?;
+        let bump_seed = arg.1.verify_address(
+            &arg.0,
+            program_id,
+            self.index_info(arg.3)?.key(),
+            &self.argument,
+        )?;
         self.seeds = Some((arg.0, bump_seed));
         Ok(())
     }
@@ -168,24 +184,26 @@ where
     }
 }
 
-/// A bump seed finder, implementations for [`u8`] and [`Find`]
-pub trait BumpSeed {
+/// A bump seed finder for the wrapped argument `T`, implementations for [`u8`], [`Find`], [`OfProgram`], and [`Stored`]
+pub trait BumpSeed<T: ?Sized> {
     /// Verifies a given address and returns the bump seed
     fn verify_address<S>(
         self,
         seeder: &S,
         program_id: &Pubkey,
         address: &Pubkey,
+        argument: &T,
     ) -> CruiserResult<u8>
     where
         S: PDASeeder;
 }
-impl BumpSeed for u8 {
+impl<T: ?Sized> BumpSeed<T> for u8 {
     fn verify_address<S>(
         self,
         seeder: &S,
         program_id: &Pubkey,
         address: &Pubkey,
+        _argument: &T,
     ) -> CruiserResult<u8>
     where
         S: PDASeeder,
@@ -197,12 +215,13 @@ impl BumpSeed for u8 {
 /// Finds a bump seed rather than using a given one. Can be very compute intensive for specific seeds.
 #[derive(Copy, Clone, Debug)]
 pub struct Find;
-impl BumpSeed for Find {
+impl<T: ?Sized> BumpSeed<T> for Find {
     fn verify_address<S>(
         self,
         seeder: &S,
         program_id: &Pubkey,
         address: &Pubkey,
+        _argument: &T,
     ) -> CruiserResult<u8>
     where
         S: PDASeeder,
@@ -210,3 +229,253 @@ impl BumpSeed for Find {
         seeder.verify_address_find_nonce(program_id, address)
     }
 }
+/// Verifies the address is a PDA of `program_id` rather than the current program, using `bump_seed` to verify.
+/// Used for accounts of other programs such as associated token accounts.
+#[derive(Copy, Clone, Debug)]
+pub struct OfProgram<B> {
+    /// The program the address is derived from
+    pub program_id: Pubkey,
+    /// The bump seed to verify with
+    pub bump_seed: B,
+}
+impl<B> OfProgram<B> {
+    /// Verifies against the program `P`
+    #[must_use]
+    pub fn of<P>(bump_seed: B) -> Self
+    where
+        P: ProgramKey,
+    {
+        Self {
+            program_id: P::KEY,
+            bump_seed,
+        }
+    }
+}
+impl<T: ?Sized, B> BumpSeed<T> for OfProgram<B>
+where
+    B: BumpSeed<T>,
+{
+    fn verify_address<S>(
+        self,
+        seeder: &S,
+        _program_id: &Pubkey,
+        address: &Pubkey,
+        argument: &T,
+    ) -> CruiserResult<u8>
+    where
+        S: PDASeeder,
+    {
+        self.bump_seed
+            .verify_address(seeder, &self.program_id, address, argument)
+    }
+}
+
+/// Account data that stores its canonical bump seed so it does not have to be found on every instruction.
+/// Used by [`Stored`] and [`StoreBump`](crate::account_types::init_account::StoreBump).
+pub trait StoredBumpSeed {
+    /// Gets the stored bump seed
+    fn bump_seed(&self) -> u8;
+    /// Sets the stored bump seed
+    fn set_bump_seed(&mut self, bump_seed: u8);
+}
+impl<AI, AL, D> StoredBumpSeed for DiscriminantAccount<AI, AL, D>
+where
+    AL: AccountListItem<D>,
+    D: StoredBumpSeed,
+{
+    fn bump_seed(&self) -> u8 {
+        D::bump_seed(self)
+    }
+
+    fn set_bump_seed(&mut self, bump_seed: u8) {
+        D::set_bump_seed(self, bump_seed);
+    }
+}
+/// Uses the bump seed stored in the account's data, costing only a single `create_program_address`.
+/// The wrapped argument must deref to a [`StoredBumpSeed`] or be a [`DataAccount`] of data implementing it.
+#[derive(Copy, Clone, Debug)]
+pub struct Stored;
+impl<T: ?Sized> BumpSeed<T> for Stored
+where
+    T: Deref,
+    T::Target: StoredBumpSeed,
+{
+    fn verify_address<S>(
+        self,
+        seeder: &S,
+        program_id: &Pubkey,
+        address: &Pubkey,
+        argument: &T,
+    ) -> CruiserResult<u8>
+    where
+        S: PDASeeder,
+    {
+        argument
+            .bump_seed()
+            .verify_address(seeder, program_id, address, argument)
+    }
+}
+
+impl<AI, AL, D> BumpSeed<DataAccount<AI, AL, D>> for Stored
+where
+    AI: AccountInfo,
+    AL: AccountListItem<D>,
+    D: BorshDeserialize + StoredBumpSeed,
+{
+    fn verify_address<S>(
+        self,
+        seeder: &S,
+        program_id: &Pubkey,
+        address: &Pubkey,
+        argument: &DataAccount<AI, AL, D>,
+    ) -> CruiserResult<u8>
+    where
+        S: PDASeeder,
+    {
+        argument
+            .try_data()?
+            .bump_seed()
+            .verify_address(seeder, program_id, address, argument)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    use crate::account_list::AccountList;
+    use crate::account_types::system_program::SystemProgram;
+    use crate::SolanaAccountInfo;
+
+    use super::*;
+
+    #[derive(AccountList)]
+    enum TestAccounts {
+        Data(TestData),
+    }
+    #[derive(Debug, BorshSerialize, BorshDeserialize)]
+    struct TestData {
+        bump: u8,
+    }
+    impl StoredBumpSeed for TestData {
+        fn bump_seed(&self) -> u8 {
+            self.bump
+        }
+
+        fn set_bump_seed(&mut self, bump_seed: u8) {
+            self.bump = bump_seed;
+        }
+    }
+
+    #[derive(Debug, PDASeeder)]
+    #[seeder(prefix = "test")]
+    struct TestSeeder {
+        key: Pubkey,
+    }
+
+    fn validate_stored(bump_offset: u8) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let seeder = TestSeeder {
+            key: Pubkey::new_unique(),
+        };
+        let (key, bump) = seeder.find_address(&program_id);
+        let mut data = <TestAccounts as AccountListItem<TestData>>::DISCRIMINANT_BYTES.to_vec();
+        TestData {
+            bump: bump.wrapping_sub(bump_offset),
+        }
+        .serialize(&mut data)
+        .unwrap();
+        let mut lamports = 0;
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        let mut account: Seeds<DataAccount<_, TestAccounts, TestData>, TestSeeder> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+        account.validate(&program_id, (seeder, Stored))?;
+        assert_eq!(account.seed_set().unwrap().nonce, [bump]);
+        Ok(())
+    }
+
+    #[test]
+    fn stored_test() {
+        validate_stored(0).unwrap();
+        assert!(validate_stored(1).is_err());
+    }
+
+    fn validate_of_program<B>(address_program: &Pubkey, bump_seed: B) -> CruiserResult<u8>
+    where
+        B: for<'a> BumpSeed<SolanaAccountInfo<'a>>,
+    {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let seeder = TestSeeder {
+            key: Pubkey::new_from_array([1; 32]),
+        };
+        let (key, _) = seeder.find_address(address_program);
+        let mut lamports = 0;
+        let mut data = [];
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let mut account: Seeds<SolanaAccountInfo, TestSeeder> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+        account.validate(&program_id, (seeder, bump_seed))?;
+        let bump_seed = account.seed_set().unwrap().nonce[0];
+        Ok(bump_seed)
+    }
+
+    #[test]
+    fn of_program_test() {
+        let other_program = Pubkey::new_unique();
+        let found = validate_of_program(
+            &other_program,
+            OfProgram {
+                program_id: other_program,
+                bump_seed: Find,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            validate_of_program(
+                &other_program,
+                OfProgram {
+                    program_id: other_program,
+                    bump_seed: found,
+                },
+            )
+            .unwrap(),
+            found
+        );
+        validate_of_program(
+            &SystemProgram::<()>::KEY,
+            OfProgram::of::<SystemProgram<()>>(Find),
+        )
+        .unwrap();
+
+        // Derived from another program than given
+        assert!(validate_of_program(
+            &other_program,
+            OfProgram {
+                program_id: Pubkey::new_unique(),
+                bump_seed: Find,
+            },
+        )
+        .is_err());
+        // Derived from another program than the current one
+        assert!(validate_of_program(&other_program, Find).is_err());
+    }
+}
@@ -0,0 +1,372 @@
+//! Verification of off-chain signed messages through the ed25519 and secp256k1 native programs.
+//!
+//! The native programs verify signatures in their own instruction, this module checks through the
+//! [`InstructionsSysvar`] that such an instruction exists in the transaction for an expected signer and message.
+//! Replay protection is provided by [`SignatureNonceAccount`].
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use solana_program::{ed25519_program, secp256k1_program};
+
+use crate::account_argument::ValidateArgument;
+use crate::account_list::AccountListItem;
+use crate::account_types::data_account::DataAccount;
+use crate::account_types::instructions_sysvar::InstructionsSysvar;
+use crate::account_types::seeds::Seeds;
+use crate::on_chain_size::{OnChainSize, OnChainStaticSize};
+use crate::pda_seeds::PDASeeder;
+use crate::{AccountInfo, CruiserResult, GenericError, ToSolanaAccountInfo};
+
+/// Size of an ed25519 public key
+pub const ED25519_PUBKEY_SIZE: usize = 32;
+/// Size of an ed25519 signature
+pub const ED25519_SIGNATURE_SIZE: usize = 64;
+/// Size of the offsets of a single ed25519 signature in the instruction data
+pub const ED25519_OFFSETS_SIZE: usize = 14;
+/// Start of the offsets in ed25519 instruction data, after the signature count and padding
+pub const ED25519_OFFSETS_START: usize = 2;
+/// Instruction index referring to the ed25519 instruction itself
+pub const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Size of an ethereum address used by secp256k1
+pub const SECP256K1_ETH_ADDRESS_SIZE: usize = 20;
+/// Size of a secp256k1 signature without the recovery id
+pub const SECP256K1_SIGNATURE_SIZE: usize = 64;
+/// Size of the offsets of a single secp256k1 signature in the instruction data
+pub const SECP256K1_OFFSETS_SIZE: usize = 11;
+/// Start of the offsets in secp256k1 instruction data, after the signature count
+pub const SECP256K1_OFFSETS_START: usize = 1;
+
+/// Where the data for a single signature lives, parsed from a native signature program instruction.
+/// Instruction indexes are absolute, [`None`] refers to the signature instruction itself.
+#[derive(Debug, Copy, Clone)]
+struct SignatureOffsets {
+    key_offset: u16,
+    key_instruction_index: Option<u16>,
+    message_offset: u16,
+    message_size: u16,
+    message_instruction_index: Option<u16>,
+}
+impl SignatureOffsets {
+    fn parse_ed25519(data: &[u8]) -> CruiserResult<Vec<Self>> {
+        let count = get_slice(data, 0, 1)?[0] as usize;
+        (0..count)
+            .map(|index| {
+                let offsets = get_slice(
+                    data,
+                    ED25519_OFFSETS_START + index * ED25519_OFFSETS_SIZE,
+                    ED25519_OFFSETS_SIZE,
+                )?;
+                let read = |start: usize| u16::from_le_bytes([offsets[start], offsets[start + 1]]);
+                let instruction_index = |start: usize| {
+                    let index = read(start);
+                    (index != ED25519_CURRENT_INSTRUCTION).then_some(index)
+                };
+                // signature_offset: u16, signature_instruction_index: u16 are not needed as the native program verified them
+                Ok(Self {
+                    key_offset: read(4),
+                    key_instruction_index: instruction_index(6),
+                    message_offset: read(8),
+                    message_size: read(10),
+                    message_instruction_index: instruction_index(12),
+                })
+            })
+            .collect()
+    }
+
+    fn parse_secp256k1(data: &[u8]) -> CruiserResult<Vec<Self>> {
+        let count = get_slice(data, 0, 1)?[0] as usize;
+        (0..count)
+            .map(|index| {
+                let offsets = get_slice(
+                    data,
+                    SECP256K1_OFFSETS_START + index * SECP256K1_OFFSETS_SIZE,
+                    SECP256K1_OFFSETS_SIZE,
+                )?;
+                let read = |start: usize| u16::from_le_bytes([offsets[start], offsets[start + 1]]);
+                // signature_offset: u16, signature_instruction_index: u8 are not needed as the native program verified them
+                Ok(Self {
+                    key_offset: read(3),
+                    key_instruction_index: Some(u16::from(offsets[5])),
+                    message_offset: read(6),
+                    message_size: read(8),
+                    message_instruction_index: Some(u16::from(offsets[10])),
+                })
+            })
+            .collect()
+    }
+}
+
+fn get_slice(data: &[u8], offset: usize, len: usize) -> CruiserResult<&[u8]> {
+    data.get(offset..offset + len).ok_or_else(|| {
+        GenericError::NotEnoughData {
+            needed: offset + len,
+            remaining: data.len(),
+        }
+        .into()
+    })
+}
+
+impl<'a, AI> InstructionsSysvar<AI>
+where
+    AI: ToSolanaAccountInfo<'a>,
+{
+    /// Returns true if the transaction contains an ed25519 program instruction verifying `message` was signed by `signer`.
+    pub fn has_ed25519_signature(&self, signer: &Pubkey, message: &[u8]) -> CruiserResult<bool> {
+        self.has_signature(
+            &ed25519_program::ID,
+            SignatureOffsets::parse_ed25519,
+            signer.as_ref(),
+            message,
+        )
+    }
+
+    /// Returns true if the transaction contains a secp256k1 program instruction verifying `message` was signed by `eth_address`.
+    pub fn has_secp256k1_signature(
+        &self,
+        eth_address: &[u8; SECP256K1_ETH_ADDRESS_SIZE],
+        message: &[u8],
+    ) -> CruiserResult<bool> {
+        self.has_signature(
+            &secp256k1_program::ID,
+            SignatureOffsets::parse_secp256k1,
+            eth_address,
+            message,
+        )
+    }
+
+    fn has_signature(
+        &self,
+        signature_program: &Pubkey,
+        parse: fn(&[u8]) -> CruiserResult<Vec<SignatureOffsets>>,
+        key: &[u8],
+        message: &[u8],
+    ) -> CruiserResult<bool> {
+        for index in 0..self.num_instructions()? as usize {
+            let instruction = self.instruction_at(index)?;
+            if &instruction.program_id != signature_program {
+                continue;
+            }
+            for offsets in parse(&instruction.data)? {
+                let load = |instruction_index: Option<u16>| -> CruiserResult<Option<Vec<u8>>> {
+                    match instruction_index {
+                        Some(other) if other as usize != index => {
+                            Ok(Some(self.instruction_at(other as usize)?.data))
+                        }
+                        _ => Ok(None),
+                    }
+                };
+                let key_data = load(offsets.key_instruction_index)?;
+                let signed_key = get_slice(
+                    key_data.as_deref().unwrap_or(&instruction.data),
+                    offsets.key_offset as usize,
+                    key.len(),
+                )?;
+                if signed_key != key || offsets.message_size as usize != message.len() {
+                    continue;
+                }
+                let message_data = load(offsets.message_instruction_index)?;
+                let signed_message = get_slice(
+                    message_data.as_deref().unwrap_or(&instruction.data),
+                    offsets.message_offset as usize,
+                    offsets.message_size as usize,
+                )?;
+                if signed_message == message {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Validates that the transaction contains an ed25519 signature of `message` by `signer`.
+/// `M` is usually [`Vec<u8>`] or `&[u8]`.
+#[derive(Debug, Clone)]
+pub struct VerifyEd25519<M> {
+    /// The expected signer of the message
+    pub signer: Pubkey,
+    /// The expected message
+    pub message: M,
+}
+impl VerifyEd25519<Vec<u8>> {
+    /// Creates a verification for a borsh serialized message
+    pub fn borsh(signer: Pubkey, message: &impl BorshSerialize) -> CruiserResult<Self> {
+        Ok(Self {
+            signer,
+            message: message.try_to_vec()?,
+        })
+    }
+}
+impl<'a, AI, M> ValidateArgument<VerifyEd25519<M>> for InstructionsSysvar<AI>
+where
+    AI: ToSolanaAccountInfo<'a>,
+    M: AsRef<[u8]>,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: VerifyEd25519<M>) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        if self.has_ed25519_signature(&arg.signer, arg.message.as_ref())? {
+            Ok(())
+        } else {
+            Err(GenericError::MissingSignatureVerification {
+                signer: arg.signer.to_string(),
+            }
+            .into())
+        }
+    }
+}
+
+/// Validates that the transaction contains a secp256k1 signature of `message` by `eth_address`.
+/// `M` is usually [`Vec<u8>`] or `&[u8]`.
+#[derive(Debug, Clone)]
+pub struct VerifySecp256k1<M> {
+    /// The expected ethereum address of the signer
+    pub eth_address: [u8; SECP256K1_ETH_ADDRESS_SIZE],
+    /// The expected message
+    pub message: M,
+}
+impl VerifySecp256k1<Vec<u8>> {
+    /// Creates a verification for a borsh serialized message
+    pub fn borsh(
+        eth_address: [u8; SECP256K1_ETH_ADDRESS_SIZE],
+        message: &impl BorshSerialize,
+    ) -> CruiserResult<Self> {
+        Ok(Self {
+            eth_address,
+            message: message.try_to_vec()?,
+        })
+    }
+}
+impl<'a, AI, M> ValidateArgument<VerifySecp256k1<M>> for InstructionsSysvar<AI>
+where
+    AI: ToSolanaAccountInfo<'a>,
+    M: AsRef<[u8]>,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: VerifySecp256k1<M>) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        if self.has_secp256k1_signature(&arg.eth_address, arg.message.as_ref())? {
+            Ok(())
+        } else {
+            Err(GenericError::MissingSignatureVerification {
+                signer: format!("{:?}", arg.eth_address),
+            }
+            .into())
+        }
+    }
+}
+
+/// Replay protection for off-chain signed messages, stores the next nonce a signer may use.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SignatureNonce {
+    /// The next valid nonce
+    pub nonce: u64,
+}
+impl OnChainSize<()> for SignatureNonce {
+    fn on_chain_max_size(_arg: ()) -> usize {
+        u64::on_chain_static_size()
+    }
+}
+
+/// The seeder for a [`SignatureNonceAccount`], seeds are `["signature_nonce", signer]`.
+#[derive(Debug, Copy, Clone, PDASeeder)]
+#[seeder(prefix = "signature_nonce")]
+pub struct SignatureNonceSeeder {
+    /// The signer the nonce is for
+    pub signer: Pubkey,
+}
+
+/// A nonce account for a signer, validated with `(SignatureNonceSeeder, B)` like any other [`Seeds`].
+/// `AL` must implement [`AccountListItem<SignatureNonce>`](AccountListItem).
+pub type SignatureNonceAccount<AI, AL> =
+    Seeds<DataAccount<AI, AL, SignatureNonce>, SignatureNonceSeeder>;
+impl<AI, AL> Seeds<DataAccount<AI, AL, SignatureNonce>, SignatureNonceSeeder>
+where
+    AI: AccountInfo,
+    AL: AccountListItem<SignatureNonce>,
+{
+    /// Consumes `nonce` from a signed message, errors if it is not the next valid nonce.
+    pub fn use_nonce(&mut self, nonce: u64) -> CruiserResult<()> {
+        let data = self.try_data_mut()?;
+        let expected = data.nonce;
+        if nonce != expected {
+            return Err(GenericError::InvalidNonce {
+                expected,
+                received: nonce,
+            }
+            .into());
+        }
+        data.nonce = expected.checked_add(1).ok_or(GenericError::InvalidNonce {
+            expected,
+            received: nonce,
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod test {
+    use super::*;
+    use crate::client::signature_verification::{ed25519_verify, secp256k1_verify};
+    use solana_sdk::signature::Signature;
+
+    #[test]
+    fn ed25519_offsets_test() {
+        let signer = Pubkey::new_unique();
+        let message = b"signed intent";
+        let instruction = ed25519_verify(&signer, &Signature::default(), message);
+        let offsets = SignatureOffsets::parse_ed25519(&instruction.data).unwrap();
+        assert_eq!(offsets.len(), 1);
+        let offsets = offsets[0];
+        assert_eq!(offsets.key_instruction_index, None);
+        assert_eq!(offsets.message_instruction_index, None);
+        assert_eq!(
+            get_slice(
+                &instruction.data,
+                offsets.key_offset as usize,
+                ED25519_PUBKEY_SIZE
+            )
+            .unwrap(),
+            signer.as_ref()
+        );
+        assert_eq!(
+            get_slice(
+                &instruction.data,
+                offsets.message_offset as usize,
+                offsets.message_size as usize
+            )
+            .unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn secp256k1_offsets_test() {
+        let eth_address = [7; SECP256K1_ETH_ADDRESS_SIZE];
+        let message = b"signed intent";
+        let instruction =
+            secp256k1_verify(&eth_address, &[3; SECP256K1_SIGNATURE_SIZE], 1, message, 2);
+        let offsets = SignatureOffsets::parse_secp256k1(&instruction.data).unwrap();
+        assert_eq!(offsets.len(), 1);
+        let offsets = offsets[0];
+        assert_eq!(offsets.key_instruction_index, Some(2));
+        assert_eq!(offsets.message_instruction_index, Some(2));
+        assert_eq!(
+            get_slice(
+                &instruction.data,
+                offsets.key_offset as usize,
+                SECP256K1_ETH_ADDRESS_SIZE
+            )
+            .unwrap(),
+            eth_address
+        );
+        assert_eq!(
+            get_slice(
+                &instruction.data,
+                offsets.message_offset as usize,
+                offsets.message_size as usize
+            )
+            .unwrap(),
+            message
+        );
+    }
+}
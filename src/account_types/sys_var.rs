@@ -28,7 +28,8 @@ where
     AI: ToSolanaAccountInfo<'a>,
     S: Sysvar,
 {
-    /// Gets the sysvar, may be unsupported for large sys vars
+    /// Gets the sysvar, may be unsupported for large sys vars.
+    /// The instructions sysvar is supported through [`InstructionsSysvar`](crate::account_types::instructions_sysvar::InstructionsSysvar).
     pub fn get(&self) -> CruiserResult<S> {
         unsafe { Ok(S::from_account_info(&self.0.to_solana_account_info())?) }
     }
@@ -4,7 +4,11 @@ use std::fmt::Debug;
 
 use solana_program::entrypoint::ProgramResult;
 use solana_program::pubkey::Pubkey;
-use solana_program::system_instruction::create_account;
+use solana_program::system_instruction::{
+    advance_nonce_account, allocate, allocate_with_seed, assign, assign_with_seed,
+    authorize_nonce_account, create_account, create_account_with_seed, create_nonce_account,
+    transfer, transfer_with_seed, withdraw_nonce_account,
+};
 
 use crate::account_argument::{AccountArgument, MultiIndexable, SingleIndexable};
 use crate::cpi::CPI;
@@ -52,6 +56,24 @@ pub struct Create<'a, AI> {
     /// The owning program of the new account
     pub owner: &'a Pubkey,
 }
+/// Argument for [`SystemProgram::create_account_with_seed`]
+#[derive(Copy, Clone, Debug)]
+pub struct CreateWithSeed<'a, AI> {
+    /// The funder of the new account
+    pub funder: &'a AI,
+    /// The account to create, derived from `base`, `seed`, and `owner`
+    pub account: &'a AI,
+    /// The base of the account's address, must sign
+    pub base: &'a AI,
+    /// The seed of the account's address
+    pub seed: &'a str,
+    /// The amount of lamports to give the new account
+    pub lamports: u64,
+    /// The amount of space to allocate to the new account
+    pub space: u64,
+    /// The owning program of the new account
+    pub owner: &'a Pubkey,
+}
 impl<'a, AI> SystemProgram<AI>
 where
     AI: ToSolanaAccountInfo<'a>,
@@ -76,6 +98,257 @@ where
             seeds,
         )
     }
+
+    /// Creates an account like [`SystemProgram::create_account`].
+    /// If the account already holds lamports the shortfall to [`Create::lamports`] is transferred and the account is allocated and assigned instead,
+    /// [`create_account`] fails on accounts with lamports so anyone could otherwise block creation by sending lamports to the address.
+    pub fn create_or_allocate_account<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI + Copy,
+        create: &Create<AI>,
+        funder_seeds: Option<&'b PDASeedSet<'c>>,
+        account_seeds: Option<&'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        let current_lamports = *create.account.lamports();
+        if current_lamports == 0 {
+            return self.create_account(cpi, create, funder_seeds.into_iter().chain(account_seeds));
+        }
+
+        if current_lamports < create.lamports {
+            self.transfer(
+                cpi,
+                create.funder,
+                create.account,
+                create.lamports - current_lamports,
+                funder_seeds,
+            )?;
+        }
+        self.allocate(cpi, create.account, create.space, account_seeds)?;
+        self.assign(cpi, create.account, create.owner, account_seeds)
+    }
+
+    /// Calls the system program's [`create_account_with_seed`] instruction with given PDA seeds.
+    pub fn create_account_with_seed<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        create: &CreateWithSeed<AI>,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &create_account_with_seed(
+                create.funder.key(),
+                create.account.key(),
+                create.base.key(),
+                create.seed,
+                create.lamports,
+                create.space,
+                create.owner,
+            ),
+            &[&self.info, create.funder, create.account, create.base],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`transfer`] instruction with given PDA seeds.
+    pub fn transfer<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        from: &AI,
+        to: &AI,
+        lamports: u64,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &transfer(from.key(), to.key(), lamports),
+            &[&self.info, from, to],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`transfer_with_seed`] instruction with given PDA seeds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_with_seed<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        from: &AI,
+        from_base: &AI,
+        from_seed: &str,
+        from_owner: &Pubkey,
+        to: &AI,
+        lamports: u64,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &transfer_with_seed(
+                from.key(),
+                from_base.key(),
+                from_seed.to_string(),
+                from_owner,
+                to.key(),
+                lamports,
+            ),
+            &[&self.info, from, from_base, to],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`assign`] instruction with given PDA seeds.
+    pub fn assign<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        account: &AI,
+        owner: &Pubkey,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &assign(account.key(), owner),
+            &[&self.info, account],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`assign_with_seed`] instruction with given PDA seeds.
+    pub fn assign_with_seed<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        account: &AI,
+        base: &AI,
+        seed: &str,
+        owner: &Pubkey,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &assign_with_seed(account.key(), base.key(), seed, owner),
+            &[&self.info, account, base],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`allocate`] instruction with given PDA seeds.
+    pub fn allocate<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        account: &AI,
+        space: u64,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &allocate(account.key(), space),
+            &[&self.info, account],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`allocate_with_seed`] instruction with given PDA seeds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn allocate_with_seed<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        account: &AI,
+        base: &AI,
+        seed: &str,
+        space: u64,
+        owner: &Pubkey,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &allocate_with_seed(account.key(), base.key(), seed, space, owner),
+            &[&self.info, account, base],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`create_nonce_account`] instructions with given PDA seeds.
+    /// Creates the nonce account and initializes it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_nonce_account<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI + Copy,
+        funder: &AI,
+        nonce: &AI,
+        authority: &Pubkey,
+        lamports: u64,
+        recent_blockhashes: &AI,
+        rent: &AI,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        let seeds = seeds.into_iter().collect::<Vec<_>>();
+        let instructions = create_nonce_account(funder.key(), nonce.key(), authority, lamports);
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &instructions[0],
+            &[&self.info, funder, nonce],
+            seeds.iter().copied(),
+        )?;
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &instructions[1],
+            &[&self.info, nonce, recent_blockhashes, rent],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`advance_nonce_account`] instruction with given PDA seeds.
+    pub fn advance_nonce_account<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        nonce: &AI,
+        authority: &AI,
+        recent_blockhashes: &AI,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &advance_nonce_account(nonce.key(), authority.key()),
+            &[&self.info, nonce, recent_blockhashes, authority],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`withdraw_nonce_account`] instruction with given PDA seeds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw_nonce_account<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        nonce: &AI,
+        authority: &AI,
+        to: &AI,
+        lamports: u64,
+        recent_blockhashes: &AI,
+        rent: &AI,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &withdraw_nonce_account(nonce.key(), authority.key(), to.key(), lamports),
+            &[&self.info, nonce, to, recent_blockhashes, rent, authority],
+            seeds,
+        )
+    }
+
+    /// Calls the system program's [`authorize_nonce_account`] instruction with given PDA seeds.
+    pub fn authorize_nonce_account<'b, 'c: 'b>(
+        &self,
+        cpi: impl CPI,
+        nonce: &AI,
+        authority: &AI,
+        new_authority: &Pubkey,
+        seeds: impl IntoIterator<Item = &'b PDASeedSet<'c>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &authorize_nonce_account(nonce.key(), authority.key(), new_authority),
+            &[&self.info, nonce, authority],
+            seeds,
+        )
+    }
 }
 impl<AI, T> MultiIndexable<T> for SystemProgram<AI>
 where
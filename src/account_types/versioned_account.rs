@@ -0,0 +1,465 @@
+//! Account data with past versions that are migrated on read
+
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::num::NonZeroU64;
+use std::ops::{Deref, DerefMut};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::account_list::AccountListItem;
+use crate::account_types::system_program::SystemProgram;
+use crate::compressed_numbers::CompressedNumber;
+use crate::cpi::CPI;
+use crate::pda_seeds::PDASeedSet;
+use crate::{AccountInfo, CruiserResult, GenericError, SafeRealloc, ToSolanaAccountInfo};
+
+// verify_account_arg_impl! {
+//     mod versioned_account_check<AI>{
+//         <AI, AL, D> VersionedAccount<AI, AL, D>
+//         where
+//             AI: AccountInfo + SafeRealloc,
+//             AL: AccountListItem<D>,
+//             D: Versioned<AL>,
+//         {
+//             from: [()];
+//             validate: [
+//                 ();
+//                 <'a, 'b, C> FundRealloc<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI;
+//             ];
+//             multi: [<I> I where AI: MultiIndexable<AI, I>];
+//             single: [<I> I where AI: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// Account data that has past versions. Each version is its own entry in the [`AccountList`](crate::account_list::AccountList) so existing accounts keep their discriminant.
+///
+/// ```ignore
+/// impl Versioned<MyAccounts> for ConfigV3 {
+///     fn migrate(discriminant: NonZeroU64, data: &mut &[u8]) -> CruiserResult<Option<Self>> {
+///         Ok(ConfigV2::read_version(discriminant, data)?.map(ConfigV3::from))
+///     }
+/// }
+/// impl Versioned<MyAccounts> for ConfigV2 {
+///     fn migrate(discriminant: NonZeroU64, data: &mut &[u8]) -> CruiserResult<Option<Self>> {
+///         Ok(ConfigV1::read_version(discriminant, data)?.map(ConfigV2::from))
+///     }
+/// }
+/// impl Versioned<MyAccounts> for ConfigV1 {
+///     fn migrate(_discriminant: NonZeroU64, _data: &mut &[u8]) -> CruiserResult<Option<Self>> {
+///         Ok(None)
+///     }
+/// }
+/// ```
+pub trait Versioned<AL>: BorshSerialize + BorshDeserialize
+where
+    AL: AccountListItem<Self>,
+{
+    /// Deserializes a past version with `discriminant` and migrates it to this version.
+    /// Returns [`None`] if `discriminant` is not a past version.
+    fn migrate(discriminant: NonZeroU64, data: &mut &[u8]) -> CruiserResult<Option<Self>>;
+
+    /// Deserializes this version or migrates a past version.
+    /// Returns [`None`] if `discriminant` is not this or a past version.
+    fn read_version(discriminant: NonZeroU64, data: &mut &[u8]) -> CruiserResult<Option<Self>> {
+        if discriminant == AL::discriminant() {
+            Ok(Some(Self::deserialize(data)?))
+        } else {
+            Self::migrate(discriminant, data)
+        }
+    }
+}
+
+/// An account owned by the current program with [`Versioned`] data.
+/// Past versions are migrated on read and written back as the latest version, growing the account if needed.
+/// Write back errors if the account would not be rent exempt at the new size, validate with [`FundRealloc`] to top up the account.
+pub struct VersionedAccount<AI, AL, D> {
+    /// The [`AccountInfo`] of this account.
+    pub info: AI,
+    data: D,
+    migrated_from: Option<NonZeroU64>,
+    rent: Option<Rent>,
+    phantom_al: PhantomData<fn() -> AL>,
+}
+impl<AI, AL, D> VersionedAccount<AI, AL, D> {
+    /// The discriminant of the version this account was migrated from, [`None`] if it was already the latest version
+    #[must_use]
+    pub fn migrated_from(&self) -> Option<NonZeroU64> {
+        self.migrated_from
+    }
+}
+impl<AI, AL, D> VersionedAccount<AI, AL, D>
+where
+    AI: AccountInfo,
+    AL: AccountListItem<D>,
+    D: Versioned<AL>,
+{
+    /// The data length needed to write back the current data
+    pub fn needed_len(&self) -> CruiserResult<usize> {
+        Ok(AL::DISCRIMINANT_BYTES.len() + self.data.try_to_vec()?.len())
+    }
+
+    fn rent(&self) -> CruiserResult<Rent> {
+        Ok(match self.rent {
+            Some(rent) => rent,
+            None => Rent::get()?,
+        })
+    }
+}
+impl<AI, AL, D> Debug for VersionedAccount<AI, AL, D>
+where
+    AI: Debug,
+    D: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VersionedAccount")
+            .field("info", &self.info)
+            .field("data", &self.data)
+            .field("migrated_from", &self.migrated_from)
+            .finish()
+    }
+}
+impl<AI, AL, D> Deref for VersionedAccount<AI, AL, D> {
+    type Target = D;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+impl<AI, AL, D> DerefMut for VersionedAccount<AI, AL, D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+impl<AI, AL, D> AccountArgument for VersionedAccount<AI, AL, D>
+where
+    AI: AccountInfo + SafeRealloc,
+    AL: AccountListItem<D>,
+    D: Versioned<AL>,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        if self.info.is_writable() {
+            let discriminant_len = AL::DISCRIMINANT_BYTES.len();
+            let data = self.data.try_to_vec()?;
+            let needed_len = discriminant_len + data.len();
+            if self.info.data().len() < needed_len {
+                let lamports = *self.info.lamports();
+                let needed_lamports = self.rent()?.minimum_balance(needed_len);
+                if lamports < needed_lamports {
+                    return Err(GenericError::NotEnoughLamports {
+                        account: *self.info.key(),
+                        lamports,
+                        needed_lamports,
+                    }
+                    .into());
+                }
+                self.info.realloc(needed_len, false)?;
+            }
+            let mut data_ref = self.info.data_mut();
+            if self.migrated_from.is_some() {
+                data_ref[..discriminant_len].copy_from_slice(AL::DISCRIMINANT_BYTES);
+            }
+            data_ref[discriminant_len..needed_len].copy_from_slice(&data);
+        }
+        self.info.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.info.add_keys(add)
+    }
+}
+impl<AI, AL, D> FromAccounts<()> for VersionedAccount<AI, AL, D>
+where
+    AI: AccountInfo + SafeRealloc,
+    AL: AccountListItem<D>,
+    D: Versioned<AL>,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, arg)?;
+        let info_data = info.data();
+        let mut data: &[u8] = &info_data;
+        let discriminant = AL::DiscriminantCompressed::deserialize(&mut data)?.into_number();
+        let value = match D::read_version(discriminant, &mut data)? {
+            Some(value) => value,
+            None => {
+                return Err(GenericError::MismatchedDiscriminant {
+                    account: *info.key(),
+                    received: discriminant.get(),
+                    expected: AL::discriminant(),
+                }
+                .into())
+            }
+        };
+        drop(info_data);
+        Ok(Self {
+            info,
+            data: value,
+            migrated_from: if discriminant == AL::discriminant() {
+                None
+            } else {
+                Some(discriminant)
+            },
+            rent: None,
+            phantom_al: PhantomData,
+        })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<AI, AL, D> ValidateArgument<()> for VersionedAccount<AI, AL, D>
+where
+    AI: AccountInfo + SafeRealloc,
+    AL: AccountListItem<D>,
+    D: Versioned<AL>,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.info.validate(program_id, arg)?;
+        let owner = *self.info.owner();
+        if &owner == program_id {
+            Ok(())
+        } else {
+            Err(GenericError::AccountOwnerNotEqual {
+                account: *self.info.key(),
+                owner,
+                expected_owner: vec![*program_id],
+            }
+            .into())
+        }
+    }
+}
+/// Validates the account and transfers enough lamports from `funder` for the account to be rent exempt at the size needed to write back the latest version.
+/// The top up uses the data as of validation, data that grows after must be funded separately.
+#[derive(Debug)]
+pub struct FundRealloc<'a, AI, C> {
+    /// The system program to transfer with
+    pub system_program: &'a SystemProgram<AI>,
+    /// The funder for the top up, must be owned by the system program
+    pub funder: &'a AI,
+    /// The seeds for the funder if PDA
+    pub funder_seeds: Option<&'a PDASeedSet<'a>>,
+    /// The rent to use, if [`None`] will use [`Rent::get`].
+    pub rent: Option<Rent>,
+    /// The CPI method to use
+    pub cpi: C,
+}
+impl<'a, 'b, AI, AL, D, C> ValidateArgument<FundRealloc<'a, AI, C>> for VersionedAccount<AI, AL, D>
+where
+    AI: ToSolanaAccountInfo<'b> + SafeRealloc,
+    AL: AccountListItem<D>,
+    D: Versioned<AL>,
+    C: CPI,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: FundRealloc<'a, AI, C>) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        self.rent = arg.rent;
+        if self.info.is_writable() {
+            let needed_lamports = self.rent()?.minimum_balance(self.needed_len()?);
+            let lamports = *self.info.lamports();
+            if lamports < needed_lamports {
+                arg.system_program.transfer(
+                    arg.cpi,
+                    arg.funder,
+                    &self.info,
+                    needed_lamports - lamports,
+                    arg.funder_seeds,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+impl<AI, AL, D, I> MultiIndexable<I> for VersionedAccount<AI, AL, D>
+where
+    AI: AccountInfo + SafeRealloc + MultiIndexable<I>,
+    AL: AccountListItem<D>,
+    D: Versioned<AL>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, AL, D, I> SingleIndexable<I> for VersionedAccount<AI, AL, D>
+where
+    AI: AccountInfo + SafeRealloc + SingleIndexable<I>,
+    AL: AccountListItem<D>,
+    D: Versioned<AL>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::slice::from_raw_parts_mut;
+
+    use solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+    use crate::account_list::AccountList;
+    use crate::CruiserAccountInfo;
+
+    use super::*;
+
+    #[derive(AccountList)]
+    enum TestAccounts {
+        V1(ConfigV1),
+        V2(ConfigV2),
+        V3(ConfigV3),
+    }
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Eq, PartialEq)]
+    struct ConfigV1 {
+        value: u8,
+    }
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Eq, PartialEq)]
+    struct ConfigV2 {
+        value: u16,
+    }
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Eq, PartialEq)]
+    struct ConfigV3 {
+        value: u16,
+        extra: u64,
+    }
+    impl From<ConfigV1> for ConfigV2 {
+        fn from(from: ConfigV1) -> Self {
+            Self {
+                value: from.value.into(),
+            }
+        }
+    }
+    impl From<ConfigV2> for ConfigV3 {
+        fn from(from: ConfigV2) -> Self {
+            Self {
+                value: from.value,
+                extra: 1,
+            }
+        }
+    }
+    impl Versioned<TestAccounts> for ConfigV3 {
+        fn migrate(discriminant: NonZeroU64, data: &mut &[u8]) -> CruiserResult<Option<Self>> {
+            Ok(ConfigV2::read_version(discriminant, data)?.map(ConfigV3::from))
+        }
+    }
+    impl Versioned<TestAccounts> for ConfigV2 {
+        fn migrate(discriminant: NonZeroU64, data: &mut &[u8]) -> CruiserResult<Option<Self>> {
+            Ok(ConfigV1::read_version(discriminant, data)?.map(ConfigV2::from))
+        }
+    }
+    impl Versioned<TestAccounts> for ConfigV1 {
+        fn migrate(_discriminant: NonZeroU64, _data: &mut &[u8]) -> CruiserResult<Option<Self>> {
+            Ok(None)
+        }
+    }
+
+    /// Lays out the data like the program input so it can be reallocated
+    fn account_info(
+        program_id: &Pubkey,
+        data: &[u8],
+        lamports: u64,
+        is_writable: bool,
+    ) -> CruiserAccountInfo {
+        let buffer = Box::leak(
+            vec![0u64; 1 + (data.len() + MAX_PERMITTED_DATA_INCREASE) / 8 + 1].into_boxed_slice(),
+        );
+        buffer[0] = data.len() as u64;
+        // Safety: the buffer is leaked and has room for the length and max data increase
+        let account_data =
+            unsafe { from_raw_parts_mut(buffer[1..].as_mut_ptr().cast::<u8>(), data.len()) };
+        account_data.copy_from_slice(data);
+        CruiserAccountInfo {
+            key: Box::leak(Box::new(Pubkey::new_unique())),
+            is_signer: false,
+            is_writable,
+            lamports: Rc::new(RefCell::new(Box::leak(Box::new(lamports)))),
+            data: Rc::new(RefCell::new(account_data)),
+            original_data_len: Box::leak(Box::new(data.len())),
+            owner: Box::leak(Box::new(RefCell::new(Box::leak(Box::new(*program_id))))),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn versioned_data<D>(data: &D) -> Vec<u8>
+    where
+        D: BorshSerialize,
+        TestAccounts: AccountListItem<D>,
+    {
+        let mut out = <TestAccounts as AccountListItem<D>>::DISCRIMINANT_BYTES.to_vec();
+        data.serialize(&mut out).unwrap();
+        out
+    }
+
+    fn write_back_v3(
+        data: &[u8],
+        lamports: u64,
+    ) -> CruiserResult<(CruiserAccountInfo, Option<NonZeroU64>)> {
+        let program_id = Pubkey::new_unique();
+        let info = account_info(&program_id, data, lamports, true);
+        let mut account: VersionedAccount<_, TestAccounts, ConfigV3> =
+            FromAccounts::from_accounts(&program_id, &mut [info.clone()].into_iter(), ())?;
+        account.rent = Some(Rent::default());
+        account.validate(&program_id, ())?;
+        let migrated_from = account.migrated_from();
+        account.write_back(&program_id)?;
+        Ok((info, migrated_from))
+    }
+
+    #[test]
+    fn migrate_test() {
+        let lamports =
+            Rent::default().minimum_balance(versioned_data(&ConfigV3 { value: 0, extra: 0 }).len());
+        let expected = versioned_data(&ConfigV3 { value: 2, extra: 1 });
+
+        for (data, discriminant) in [
+            (
+                versioned_data(&ConfigV1 { value: 2 }),
+                <TestAccounts as AccountListItem<ConfigV1>>::discriminant(),
+            ),
+            (
+                versioned_data(&ConfigV2 { value: 2 }),
+                <TestAccounts as AccountListItem<ConfigV2>>::discriminant(),
+            ),
+        ] {
+            let (info, migrated_from) = write_back_v3(&data, lamports).unwrap();
+            assert_eq!(migrated_from, Some(discriminant));
+            // Written back as the latest version
+            assert_eq!(&**info.data.borrow(), expected.as_slice());
+        }
+
+        let (info, migrated_from) = write_back_v3(&expected, lamports).unwrap();
+        assert_eq!(migrated_from, None);
+        assert_eq!(&**info.data.borrow(), expected.as_slice());
+    }
+
+    #[test]
+    fn realloc_rent_test() {
+        let data = versioned_data(&ConfigV1 { value: 2 });
+        let lamports = Rent::default().minimum_balance(data.len());
+        assert!(write_back_v3(&data, lamports).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! A wrapper that requires its account to be writable
+
+use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
+
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::util::assert::assert_is_writable;
+use crate::{AccountInfo, CruiserResult};
+
+// verify_account_arg_impl! {
+//     mod writable_check<AI>{
+//         <T> Writable<T> where T: AccountArgument<AI>{
+//             from: [
+//                 <Arg> Arg where T: FromAccounts<Arg>;
+//             ];
+//             validate: [
+//                 /// Requires all accounts to be writable.
+//                 () where T::AccountInfo: AccountInfo, T: ValidateArgument<()> + MultiIndexable<()>;
+//                 /// Requires all accounts to be writable.
+//                 <Arg> (Arg,) where T::AccountInfo: AccountInfo, T: ValidateArgument<Arg> + MultiIndexable<()>;
+//                 /// Requires the accounts at the indexer to be writable.
+//                 <Arg, I> (Arg, I) where T::AccountInfo: AccountInfo, T: ValidateArgument<Arg> + MultiIndexable<I>, I: Debug + Clone;
+//             ];
+//             multi: [<I> I where T: MultiIndexable<I>];
+//             single: [<I> I where T: SingleIndexable<I>];
+//         }
+//     }
+// }
+
+/// A wrapper that ensures the wrapped argument is writable.
+/// Functions can take `&Writable<AI>` to require that the writable check was done.
+#[derive(Debug)]
+pub struct Writable<T>(T);
+impl<T> Writable<T> {
+    /// Unwraps the inner argument
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T> Deref for Writable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<T> DerefMut for Writable<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<T> AccountArgument for Writable<T>
+where
+    T: AccountArgument,
+{
+    type AccountInfo = T::AccountInfo;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.0.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.0.add_keys(add)
+    }
+}
+impl<T, Arg> FromAccounts<Arg> for Writable<T>
+where
+    T: FromAccounts<Arg>,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = T::AccountInfo>,
+        arg: Arg,
+    ) -> CruiserResult<Self> {
+        Ok(Self(T::from_accounts(program_id, infos, arg)?))
+    }
+
+    fn accounts_usage_hint(arg: &Arg) -> (usize, Option<usize>) {
+        T::accounts_usage_hint(arg)
+    }
+}
+impl<T> ValidateArgument<()> for Writable<T>
+where
+    T::AccountInfo: AccountInfo,
+    T: ValidateArgument<()> + MultiIndexable<()>,
+{
+    fn validate(&mut self, program_id: &Pubkey, _arg: ()) -> CruiserResult<()> {
+        self.validate(program_id, ((), ()))
+    }
+}
+impl<T, Arg> ValidateArgument<(Arg,)> for Writable<T>
+where
+    T::AccountInfo: AccountInfo,
+    T: ValidateArgument<Arg> + MultiIndexable<()>,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: (Arg,)) -> CruiserResult<()> {
+        self.validate(program_id, (arg.0, ()))
+    }
+}
+impl<T, Arg, I> ValidateArgument<(Arg, I)> for Writable<T>
+where
+    T::AccountInfo: AccountInfo,
+    T: ValidateArgument<Arg> + MultiIndexable<I>,
+    I: Debug + Clone,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: (Arg, I)) -> CruiserResult<()> {
+        self.0.validate(program_id, arg.0)?;
+        assert_is_writable(&self.0, arg.1)
+    }
+}
+impl<T, Arg> MultiIndexable<Arg> for Writable<T>
+where
+    T: MultiIndexable<Arg>,
+{
+    #[inline]
+    fn index_is_signer(&self, indexer: Arg) -> CruiserResult<bool> {
+        self.0.index_is_signer(indexer)
+    }
+
+    #[inline]
+    fn index_is_writable(&self, indexer: Arg) -> CruiserResult<bool> {
+        self.0.index_is_writable(indexer)
+    }
+
+    #[inline]
+    fn index_is_owner(&self, owner: &Pubkey, indexer: Arg) -> CruiserResult<bool> {
+        self.0.index_is_owner(owner, indexer)
+    }
+}
+impl<T, Arg> SingleIndexable<Arg> for Writable<T>
+where
+    T: SingleIndexable<Arg>,
+{
+    #[inline]
+    fn index_info(&self, indexer: Arg) -> CruiserResult<&Self::AccountInfo> {
+        self.0.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SolanaAccountInfo;
+
+    fn validate(is_writable: bool) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![];
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            is_writable,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let mut account: Writable<SolanaAccountInfo> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+        account.validate(&program_id, ())?;
+        assert_eq!(account.into_inner().key, &key);
+        Ok(())
+    }
+
+    #[test]
+    fn writable_test() {
+        validate(true).unwrap();
+        assert!(validate(false).is_err());
+    }
+}
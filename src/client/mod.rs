@@ -1,5 +1,6 @@
 //! Functions to make client building easier
 
+pub mod signature_verification;
 pub mod system_program;
 #[cfg(feature = "spl-token")]
 pub mod token;
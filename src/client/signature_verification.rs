@@ -0,0 +1,97 @@
+//! Client functions for the ed25519 and secp256k1 signature verification programs.
+//! These build the instructions checked by [`VerifyEd25519`](crate::account_types::signature_verification::VerifyEd25519)
+//! and [`VerifySecp256k1`](crate::account_types::signature_verification::VerifySecp256k1).
+
+use crate::account_types::signature_verification::{
+    ED25519_OFFSETS_SIZE, ED25519_OFFSETS_START, ED25519_PUBKEY_SIZE, ED25519_SIGNATURE_SIZE,
+    SECP256K1_ETH_ADDRESS_SIZE, SECP256K1_OFFSETS_SIZE, SECP256K1_OFFSETS_START,
+    SECP256K1_SIGNATURE_SIZE,
+};
+use crate::{CruiserResult, SolanaInstruction};
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+use solana_program::{ed25519_program, secp256k1_program};
+use solana_sdk::signature::{Signature, Signer};
+
+/// Creates an ed25519 program instruction verifying `signature` of `message` by `signer`.
+/// The signature is usually created off-chain by the signer with [`Signer::sign_message`].
+#[must_use]
+pub fn ed25519_verify(signer: &Pubkey, signature: &Signature, message: &[u8]) -> SolanaInstruction {
+    let key_offset = ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE;
+    let signature_offset = key_offset + ED25519_PUBKEY_SIZE;
+    let message_offset = signature_offset + ED25519_SIGNATURE_SIZE;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    // Signature count and padding
+    data.extend_from_slice(&[1, 0]);
+    for value in [
+        signature_offset as u16,
+        u16::MAX,
+        key_offset as u16,
+        u16::MAX,
+        message_offset as u16,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    SolanaInstruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Signs `message` with `signer` and creates the matching ed25519 program instruction.
+#[must_use]
+pub fn ed25519_sign(signer: &impl Signer, message: &[u8]) -> SolanaInstruction {
+    ed25519_verify(&signer.pubkey(), &signer.sign_message(message), message)
+}
+
+/// Signs a borsh serialized `message` with `signer` and creates the matching ed25519 program instruction.
+pub fn ed25519_sign_borsh(
+    signer: &impl Signer,
+    message: &impl BorshSerialize,
+) -> CruiserResult<SolanaInstruction> {
+    Ok(ed25519_sign(signer, &message.try_to_vec()?))
+}
+
+/// Creates a secp256k1 program instruction verifying `signature` of `message` by `eth_address`.
+/// `instruction_index` is the index this instruction will have in the transaction.
+#[must_use]
+pub fn secp256k1_verify(
+    eth_address: &[u8; SECP256K1_ETH_ADDRESS_SIZE],
+    signature: &[u8; SECP256K1_SIGNATURE_SIZE],
+    recovery_id: u8,
+    message: &[u8],
+    instruction_index: u8,
+) -> SolanaInstruction {
+    let eth_address_offset = SECP256K1_OFFSETS_START + SECP256K1_OFFSETS_SIZE;
+    let signature_offset = eth_address_offset + SECP256K1_ETH_ADDRESS_SIZE;
+    let message_offset = signature_offset + SECP256K1_SIGNATURE_SIZE + 1;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    // Signature count
+    data.push(1);
+    data.extend_from_slice(&(signature_offset as u16).to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(&(eth_address_offset as u16).to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(&(message_offset as u16).to_le_bytes());
+    data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(eth_address);
+    data.extend_from_slice(signature);
+    data.push(recovery_id);
+    data.extend_from_slice(message);
+
+    SolanaInstruction {
+        program_id: secp256k1_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
@@ -1,7 +1,7 @@
 //! Client functions for the system program
 
 use crate::client::HashedSigner;
-use crate::SolanaInstruction;
+use crate::{CruiserResult, SolanaInstruction};
 use solana_program::pubkey::Pubkey;
 use solana_program::system_instruction;
 use solana_sdk::signature::Signer;
@@ -46,3 +46,217 @@ pub fn transfer<'a>(
         [from],
     )
 }
+
+/// Creates a new account with an address derived from `base`, `seed`, and `owner`
+pub fn create_account_with_seed<'a>(
+    from: impl Into<HashedSigner<'a>>,
+    base: impl Into<HashedSigner<'a>>,
+    seed: &str,
+    lamports: u64,
+    space: u64,
+    owner: Pubkey,
+) -> CruiserResult<(
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+)> {
+    let from = from.into();
+    let base = base.into();
+    let to = Pubkey::create_with_seed(&base.pubkey(), seed, &owner)?;
+    Ok((
+        [system_instruction::create_account_with_seed(
+            &from.pubkey(),
+            &to,
+            &base.pubkey(),
+            seed,
+            lamports,
+            space,
+            &owner,
+        )],
+        [from, base],
+    ))
+}
+
+/// Transfers SOL from an account with an address derived from `from_base`, `from_seed`, and `from_owner`
+pub fn transfer_with_seed<'a>(
+    from_base: impl Into<HashedSigner<'a>>,
+    from_seed: &str,
+    from_owner: Pubkey,
+    to: Pubkey,
+    lamports: u64,
+) -> CruiserResult<(
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+)> {
+    let from_base = from_base.into();
+    let from = Pubkey::create_with_seed(&from_base.pubkey(), from_seed, &from_owner)?;
+    Ok((
+        [system_instruction::transfer_with_seed(
+            &from,
+            &from_base.pubkey(),
+            from_seed.to_string(),
+            &from_owner,
+            &to,
+            lamports,
+        )],
+        [from_base],
+    ))
+}
+
+/// Assigns an account to a program
+pub fn assign<'a>(
+    account: impl Into<HashedSigner<'a>>,
+    owner: Pubkey,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let account = account.into();
+    (
+        [system_instruction::assign(&account.pubkey(), &owner)],
+        [account],
+    )
+}
+
+/// Assigns an account with an address derived from `base`, `seed`, and `owner` to `owner`
+pub fn assign_with_seed<'a>(
+    base: impl Into<HashedSigner<'a>>,
+    seed: &str,
+    owner: Pubkey,
+) -> CruiserResult<(
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+)> {
+    let base = base.into();
+    let account = Pubkey::create_with_seed(&base.pubkey(), seed, &owner)?;
+    Ok((
+        [system_instruction::assign_with_seed(
+            &account,
+            &base.pubkey(),
+            seed,
+            &owner,
+        )],
+        [base],
+    ))
+}
+
+/// Allocates space for an account
+pub fn allocate<'a>(
+    account: impl Into<HashedSigner<'a>>,
+    space: u64,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let account = account.into();
+    (
+        [system_instruction::allocate(&account.pubkey(), space)],
+        [account],
+    )
+}
+
+/// Allocates space for an account with an address derived from `base`, `seed`, and `owner` and assigns it to `owner`
+pub fn allocate_with_seed<'a>(
+    base: impl Into<HashedSigner<'a>>,
+    seed: &str,
+    space: u64,
+    owner: Pubkey,
+) -> CruiserResult<(
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+)> {
+    let base = base.into();
+    let account = Pubkey::create_with_seed(&base.pubkey(), seed, &owner)?;
+    Ok((
+        [system_instruction::allocate_with_seed(
+            &account,
+            &base.pubkey(),
+            seed,
+            space,
+            &owner,
+        )],
+        [base],
+    ))
+}
+
+/// Creates and initializes a durable nonce account
+pub fn create_nonce_account<'a>(
+    from: impl Into<HashedSigner<'a>>,
+    nonce: impl Into<HashedSigner<'a>>,
+    authority: Pubkey,
+    lamports: u64,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let from = from.into();
+    let nonce = nonce.into();
+    (
+        system_instruction::create_nonce_account(
+            &from.pubkey(),
+            &nonce.pubkey(),
+            &authority,
+            lamports,
+        ),
+        [from, nonce],
+    )
+}
+
+/// Advances the nonce of a durable nonce account
+pub fn advance_nonce_account<'a>(
+    nonce: Pubkey,
+    authority: impl Into<HashedSigner<'a>>,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let authority = authority.into();
+    (
+        [system_instruction::advance_nonce_account(
+            &nonce,
+            &authority.pubkey(),
+        )],
+        [authority],
+    )
+}
+
+/// Withdraws SOL from a durable nonce account
+pub fn withdraw_nonce_account<'a>(
+    nonce: Pubkey,
+    authority: impl Into<HashedSigner<'a>>,
+    to: Pubkey,
+    lamports: u64,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let authority = authority.into();
+    (
+        [system_instruction::withdraw_nonce_account(
+            &nonce,
+            &authority.pubkey(),
+            &to,
+            lamports,
+        )],
+        [authority],
+    )
+}
+
+/// Changes the authority of a durable nonce account
+pub fn authorize_nonce_account<'a>(
+    nonce: Pubkey,
+    authority: impl Into<HashedSigner<'a>>,
+    new_authority: Pubkey,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let authority = authority.into();
+    (
+        [system_instruction::authorize_nonce_account(
+            &nonce,
+            &authority.pubkey(),
+            &new_authority,
+        )],
+        [authority],
+    )
+}
@@ -3,13 +3,18 @@
 use crate::client::HashedSigner;
 use crate::on_chain_size::OnChainStaticSize;
 use crate::program::ProgramKey;
-use crate::spl::token::{MintAccount, TokenAccount, TokenProgram};
+pub use crate::spl::token::get_associated_token_address;
+use crate::spl::token::{
+    create_associated_token_account_instruction, MintAccount, TokenAccount, TokenProgram,
+};
 use cruiser::SolanaInstruction;
 use solana_program::pubkey::Pubkey;
 use solana_program::system_instruction::create_account;
 use solana_sdk::signature::Signer;
 use spl_token::instruction;
+use spl_token::instruction::AuthorityType;
 use std::future::Future;
+use std::iter::empty;
 
 /// Creates a new token account
 #[allow(clippy::missing_panics_doc)]
@@ -150,3 +155,323 @@ pub fn transfer<'a>(
         [authority],
     )
 }
+
+/// Mints tokens to an account, checking the mint's decimals
+#[allow(clippy::missing_panics_doc)]
+pub fn mint_to_checked<'a>(
+    mint: Pubkey,
+    token_account_to: Pubkey,
+    mint_authority: impl Into<HashedSigner<'a>>,
+    amount: u64,
+    decimals: u8,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let mint_authority = mint_authority.into();
+    (
+        [instruction::mint_to_checked(
+            &TokenProgram::<()>::KEY,
+            &mint,
+            &token_account_to,
+            &mint_authority.pubkey(),
+            &[],
+            amount,
+            decimals,
+        )
+        .unwrap()],
+        [mint_authority],
+    )
+}
+
+/// Transfers tokens between accounts, checking the mint and its decimals
+#[allow(clippy::missing_panics_doc)]
+pub fn transfer_checked<'a>(
+    source_account: Pubkey,
+    mint: Pubkey,
+    destination_account: Pubkey,
+    authority: impl Into<HashedSigner<'a>>,
+    amount: u64,
+    decimals: u8,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let authority = authority.into();
+    (
+        [instruction::transfer_checked(
+            &TokenProgram::<()>::KEY,
+            &source_account,
+            &mint,
+            &destination_account,
+            &authority.pubkey(),
+            &[],
+            amount,
+            decimals,
+        )
+        .unwrap()],
+        [authority],
+    )
+}
+
+/// Burns tokens from an account
+#[allow(clippy::missing_panics_doc)]
+pub fn burn<'a>(
+    token_account: Pubkey,
+    mint: Pubkey,
+    authority: impl Into<HashedSigner<'a>>,
+    amount: u64,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let authority = authority.into();
+    (
+        [instruction::burn(
+            &TokenProgram::<()>::KEY,
+            &token_account,
+            &mint,
+            &authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        [authority],
+    )
+}
+
+/// Burns tokens from an account, checking the mint's decimals
+#[allow(clippy::missing_panics_doc)]
+pub fn burn_checked<'a>(
+    token_account: Pubkey,
+    mint: Pubkey,
+    authority: impl Into<HashedSigner<'a>>,
+    amount: u64,
+    decimals: u8,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let authority = authority.into();
+    (
+        [instruction::burn_checked(
+            &TokenProgram::<()>::KEY,
+            &token_account,
+            &mint,
+            &authority.pubkey(),
+            &[],
+            amount,
+            decimals,
+        )
+        .unwrap()],
+        [authority],
+    )
+}
+
+/// Approves a delegate to transfer tokens from an account
+#[allow(clippy::missing_panics_doc)]
+pub fn approve<'a>(
+    token_account: Pubkey,
+    delegate: Pubkey,
+    owner: impl Into<HashedSigner<'a>>,
+    amount: u64,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let owner = owner.into();
+    (
+        [instruction::approve(
+            &TokenProgram::<()>::KEY,
+            &token_account,
+            &delegate,
+            &owner.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        [owner],
+    )
+}
+
+/// Revokes the delegate of an account
+#[allow(clippy::missing_panics_doc)]
+pub fn revoke<'a>(
+    token_account: Pubkey,
+    owner: impl Into<HashedSigner<'a>>,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let owner = owner.into();
+    (
+        [instruction::revoke(
+            &TokenProgram::<()>::KEY,
+            &token_account,
+            &owner.pubkey(),
+            &[],
+        )
+        .unwrap()],
+        [owner],
+    )
+}
+
+/// Freezes an account
+#[allow(clippy::missing_panics_doc)]
+pub fn freeze_account<'a>(
+    token_account: Pubkey,
+    mint: Pubkey,
+    freeze_authority: impl Into<HashedSigner<'a>>,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let freeze_authority = freeze_authority.into();
+    (
+        [instruction::freeze_account(
+            &TokenProgram::<()>::KEY,
+            &token_account,
+            &mint,
+            &freeze_authority.pubkey(),
+            &[],
+        )
+        .unwrap()],
+        [freeze_authority],
+    )
+}
+
+/// Thaws a frozen account
+#[allow(clippy::missing_panics_doc)]
+pub fn thaw_account<'a>(
+    token_account: Pubkey,
+    mint: Pubkey,
+    freeze_authority: impl Into<HashedSigner<'a>>,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let freeze_authority = freeze_authority.into();
+    (
+        [instruction::thaw_account(
+            &TokenProgram::<()>::KEY,
+            &token_account,
+            &mint,
+            &freeze_authority.pubkey(),
+            &[],
+        )
+        .unwrap()],
+        [freeze_authority],
+    )
+}
+
+/// Syncs the amount of a native token account with its lamports
+#[must_use]
+#[allow(clippy::missing_panics_doc)]
+pub fn sync_native<'a>(
+    token_account: Pubkey,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    (
+        [instruction::sync_native(&TokenProgram::<()>::KEY, &token_account).unwrap()],
+        empty::<HashedSigner<'a>>(),
+    )
+}
+
+/// Initializes an existing token account, the owner is given as instruction data so no rent sysvar is needed
+#[must_use]
+#[allow(clippy::missing_panics_doc)]
+pub fn initialize_account3<'a>(
+    token_account: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    (
+        [
+            instruction::initialize_account3(
+                &TokenProgram::<()>::KEY,
+                &token_account,
+                &mint,
+                &owner,
+            )
+            .unwrap(),
+        ],
+        empty::<HashedSigner<'a>>(),
+    )
+}
+
+/// Sets or removes an authority of a mint or account
+#[allow(clippy::missing_panics_doc)]
+pub fn set_authority<'a>(
+    owned: Pubkey,
+    new_authority: Option<Pubkey>,
+    authority_type: AuthorityType,
+    current_authority: impl Into<HashedSigner<'a>>,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let current_authority = current_authority.into();
+    (
+        [instruction::set_authority(
+            &TokenProgram::<()>::KEY,
+            &owned,
+            new_authority.as_ref(),
+            authority_type,
+            &current_authority.pubkey(),
+            &[],
+        )
+        .unwrap()],
+        [current_authority],
+    )
+}
+
+/// Closes an account, sending its lamports to `destination`
+#[allow(clippy::missing_panics_doc)]
+pub fn close_account<'a>(
+    token_account: Pubkey,
+    destination: Pubkey,
+    authority: impl Into<HashedSigner<'a>>,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let authority = authority.into();
+    (
+        [instruction::close_account(
+            &TokenProgram::<()>::KEY,
+            &token_account,
+            &destination,
+            &authority.pubkey(),
+            &[],
+        )
+        .unwrap()],
+        [authority],
+    )
+}
+
+/// Creates the associated token account of `wallet` for `mint`.
+/// Use [`get_associated_token_address`] with the [`TokenProgram`] to get the new account's address.
+pub fn create_associated_token_account<'a>(
+    funder: impl Into<HashedSigner<'a>>,
+    wallet: Pubkey,
+    mint: Pubkey,
+) -> (
+    impl IntoIterator<Item = SolanaInstruction>,
+    impl IntoIterator<Item = HashedSigner<'a>>,
+) {
+    let funder = funder.into();
+    (
+        [create_associated_token_account_instruction(
+            &funder.pubkey(),
+            &wallet,
+            &TokenProgram::<()>::KEY,
+            &mint,
+        )],
+        [funder],
+    )
+}
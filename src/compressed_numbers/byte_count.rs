@@ -1,4 +1,6 @@
-use crate::compressed_numbers::CompressedNumber;
+use crate::compressed_numbers::{
+    CompressedNumber, ConstBytes, ConstCompressedNumber, MAX_CONST_BYTES,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use cruiser::util::bytes_ext::{ReadExt, WriteExt};
 use std::io::Write;
@@ -26,6 +28,24 @@ impl ByteCount<u64> {
             1
         }
     }
+
+    const fn const_bytes(self) -> ConstBytes {
+        let mut out = [0; MAX_CONST_BYTES];
+        if self.0 >= Self::COUNT_BIT as u64 {
+            let count = size_of::<u64>() - self.0.leading_zeros() as usize / 8;
+            let bytes = self.0.to_le_bytes();
+            out[0] = count as u8 | Self::COUNT_BIT;
+            let mut index = 0;
+            while index < count {
+                out[index + 1] = bytes[index];
+                index += 1;
+            }
+            ConstBytes::new(out, count + 1)
+        } else {
+            out[0] = self.0 as u8;
+            ConstBytes::new(out, 1)
+        }
+    }
 }
 unsafe impl CompressedNumber for ByteCount<u64> {
     type Num = u64;
@@ -50,6 +70,9 @@ unsafe impl CompressedNumber for ByteCount<u64> {
         9
     }
 }
+unsafe impl<const N: u64> ConstCompressedNumber<N> for ByteCount<u64> {
+    const BYTES: ConstBytes = Self::from_u64(N).const_bytes();
+}
 impl BorshSerialize for ByteCount<u64> {
     fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         if self.0 > u64::from(!Self::COUNT_BIT) {
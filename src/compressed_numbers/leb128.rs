@@ -0,0 +1,331 @@
+use crate::compressed_numbers::{
+    CompressedNumber, ConstBytes, ConstCompressedNumber, MAX_CONST_BYTES,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use cruiser::util::bytes_ext::{ReadExt, WriteExt};
+use std::io::{Error, ErrorKind, Write};
+use std::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64};
+
+/// A [LEB128](https://en.wikipedia.org/wiki/LEB128) variable length number.
+/// Each byte holds 7 bits of the number with the high bit set if more bytes follow.
+/// Signed numbers are [ZigZag](https://developers.google.com/protocol-buffers/docs/encoding#signed-ints) encoded so small negative numbers stay small.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Leb128<T>(T);
+
+const CONTINUE_BIT: u8 = 1 << 7;
+
+const fn leb128_bytes(bits: u32) -> usize {
+    if bits == 0 {
+        1
+    } else {
+        ((bits + 6) / 7) as usize
+    }
+}
+
+const fn leb128_const(mut value: u64) -> ConstBytes {
+    let mut out = [0; MAX_CONST_BYTES];
+    let mut index = 0;
+    while value >= CONTINUE_BIT as u64 {
+        out[index] = value as u8 | CONTINUE_BIT;
+        value >>= 7;
+        index += 1;
+    }
+    out[index] = value as u8;
+    ConstBytes::new(out, index + 1)
+}
+
+fn overflow_error() -> Error {
+    Error::new(ErrorKind::InvalidData, "LEB128 number overflows its type")
+}
+
+fn over_long_error() -> Error {
+    Error::new(ErrorKind::InvalidData, "LEB128 number is not minimally encoded")
+}
+
+macro_rules! impl_leb128_unsigned {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl Leb128<$ty> {
+                const fn num_bytes(self) -> usize {
+                    leb128_bytes(<$ty>::BITS - self.0.leading_zeros())
+                }
+            }
+            unsafe impl CompressedNumber for Leb128<$ty> {
+                type Num = $ty;
+
+                #[inline]
+                fn from_number(number: Self::Num) -> Self {
+                    Self(number)
+                }
+
+                #[inline]
+                fn into_number(self) -> Self::Num {
+                    self.0
+                }
+
+                #[inline]
+                fn num_bytes(self) -> usize {
+                    self.num_bytes()
+                }
+
+                #[inline]
+                fn max_bytes() -> usize {
+                    leb128_bytes(<$ty>::BITS)
+                }
+            }
+            impl BorshSerialize for Leb128<$ty> {
+                fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                    let mut value = self.0;
+                    while value >= <$ty>::from(CONTINUE_BIT) {
+                        writer.write_u8(value as u8 | CONTINUE_BIT)?;
+                        value >>= 7;
+                    }
+                    writer.write_u8(value as u8)
+                }
+            }
+            impl BorshDeserialize for Leb128<$ty> {
+                fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                    let mut value: $ty = 0;
+                    let mut shift = 0;
+                    loop {
+                        let byte = buf.read_u8()?;
+                        let bits = <$ty>::from(byte & !CONTINUE_BIT);
+                        if shift >= <$ty>::BITS || (bits << shift) >> shift != bits {
+                            return Err(overflow_error());
+                        }
+                        value |= bits << shift;
+                        if byte & CONTINUE_BIT == 0 {
+                            // A trailing zero byte could have been left off
+                            return if byte == 0 && shift > 0 {
+                                Err(over_long_error())
+                            } else {
+                                Ok(Self(value))
+                            };
+                        }
+                        shift += 7;
+                    }
+                }
+            }
+        )+
+    };
+}
+impl_leb128_unsigned!(u16, u32, u64, u128);
+unsafe impl<const N: u64> ConstCompressedNumber<N> for Leb128<u64> {
+    const BYTES: ConstBytes = leb128_const(N);
+}
+
+macro_rules! impl_leb128_signed {
+    ($($ty:ty => $unsigned:ty),+ $(,)?) => {
+        $(
+            #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+            impl Leb128<$ty> {
+                const fn zig_zag(self) -> Leb128<$unsigned> {
+                    Leb128(((self.0 << 1) ^ (self.0 >> (<$ty>::BITS - 1))) as $unsigned)
+                }
+
+                const fn from_zig_zag(zig_zag: Leb128<$unsigned>) -> Self {
+                    Self(((zig_zag.0 >> 1) as $ty) ^ -((zig_zag.0 & 1) as $ty))
+                }
+
+                const fn num_bytes(self) -> usize {
+                    self.zig_zag().num_bytes()
+                }
+            }
+            unsafe impl CompressedNumber for Leb128<$ty> {
+                type Num = $ty;
+
+                #[inline]
+                fn from_number(number: Self::Num) -> Self {
+                    Self(number)
+                }
+
+                #[inline]
+                fn into_number(self) -> Self::Num {
+                    self.0
+                }
+
+                #[inline]
+                fn num_bytes(self) -> usize {
+                    self.num_bytes()
+                }
+
+                #[inline]
+                fn max_bytes() -> usize {
+                    Leb128::<$unsigned>::max_bytes()
+                }
+            }
+            impl BorshSerialize for Leb128<$ty> {
+                fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                    self.zig_zag().serialize(writer)
+                }
+            }
+            impl BorshDeserialize for Leb128<$ty> {
+                fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                    Leb128::<$unsigned>::deserialize(buf).map(Self::from_zig_zag)
+                }
+            }
+        )+
+    };
+}
+impl_leb128_signed!(i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+macro_rules! impl_leb128_non_zero {
+    ($($ty:ty => $inner:ty),+ $(,)?) => {
+        $(
+            impl Leb128<$ty> {
+                const fn num_bytes(self) -> usize {
+                    Leb128(self.0.get()).num_bytes()
+                }
+            }
+            unsafe impl CompressedNumber for Leb128<$ty> {
+                type Num = $ty;
+
+                #[inline]
+                fn from_number(number: Self::Num) -> Self {
+                    Self(number)
+                }
+
+                #[inline]
+                fn into_number(self) -> Self::Num {
+                    self.0
+                }
+
+                #[inline]
+                fn num_bytes(self) -> usize {
+                    self.num_bytes()
+                }
+
+                #[inline]
+                fn max_bytes() -> usize {
+                    Leb128::<$inner>::max_bytes()
+                }
+            }
+            impl BorshSerialize for Leb128<$ty> {
+                fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                    Leb128(self.0.get()).serialize(writer)
+                }
+            }
+            impl BorshDeserialize for Leb128<$ty> {
+                fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                    <$ty>::new(Leb128::<$inner>::deserialize(buf)?.0)
+                        .map(Self)
+                        .ok_or_else(|| {
+                            Error::new(ErrorKind::InvalidData, "Zero value for non-zero number")
+                        })
+                }
+            }
+        )+
+    };
+}
+impl_leb128_non_zero!(
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+);
+unsafe impl<const N: u64> ConstCompressedNumber<N> for Leb128<NonZeroU64> {
+    const BYTES: ConstBytes = {
+        assert!(N != 0, "Zero value for non-zero number");
+        leb128_const(N)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::distributions::{Distribution, Standard};
+    use rand::{thread_rng, Rng};
+    use std::fmt::Debug;
+    use std::ops::Shr;
+
+    fn round_trip<T>(value: T::Num)
+    where
+        T: CompressedNumber + Debug,
+        T::Num: Copy + Debug + PartialEq,
+    {
+        let before = T::from_number(value);
+        let bytes = before.try_to_vec().unwrap();
+        assert_eq!(bytes.len(), before.num_bytes(), "Value: {:?}", value);
+        assert!(bytes.len() <= T::max_bytes());
+        let after = T::try_from_slice(&bytes).unwrap_or_else(|error| {
+            panic!(
+                "Error encountered: {}\n number: {:?}, bytes: {:?}",
+                error, value, bytes
+            )
+        });
+        assert_eq!(value, after.into_number(), "Bytes: {:?}", bytes);
+    }
+
+    fn random_round_trip<N>(bits: u32)
+    where
+        Leb128<N>: CompressedNumber<Num = N> + Debug,
+        N: Copy + Debug + PartialEq + Shr<u32, Output = N>,
+        Standard: Distribution<N>,
+    {
+        let mut rng = thread_rng();
+        for _ in 0..1 << 14 {
+            round_trip::<Leb128<N>>(rng.gen::<N>() >> rng.gen_range(0, bits));
+        }
+    }
+
+    #[test]
+    fn unsigned_test() {
+        random_round_trip::<u16>(u16::BITS);
+        random_round_trip::<u32>(u32::BITS);
+        random_round_trip::<u64>(u64::BITS);
+        random_round_trip::<u128>(u128::BITS);
+        for value in [0, 1, 127, 128, 16383, 16384, u64::MAX] {
+            round_trip::<Leb128<u64>>(value);
+        }
+        round_trip::<Leb128<u128>>(u128::MAX);
+    }
+
+    #[test]
+    fn signed_test() {
+        random_round_trip::<i16>(i16::BITS);
+        random_round_trip::<i32>(i32::BITS);
+        random_round_trip::<i64>(i64::BITS);
+        random_round_trip::<i128>(i128::BITS);
+        for value in [0, -1, 1, -64, 63, -65, 64, i64::MIN, i64::MAX] {
+            round_trip::<Leb128<i64>>(value);
+        }
+        assert_eq!(Leb128(-64i64).num_bytes(), 1);
+        assert_eq!(Leb128(-65i64).num_bytes(), 2);
+    }
+
+    #[test]
+    fn non_zero_test() {
+        let mut rng = thread_rng();
+        for _ in 0..1 << 14 {
+            let value = (rng.gen::<u64>() >> rng.gen_range(0, u64::BITS)).max(1);
+            round_trip::<Leb128<NonZeroU64>>(NonZeroU64::new(value).unwrap());
+        }
+        assert!(Leb128::<NonZeroU64>::try_from_slice(&[0]).is_err());
+    }
+
+    #[test]
+    fn overflow_test() {
+        assert!(Leb128::<u16>::try_from_slice(&[0xFF, 0xFF, 0x04]).is_err());
+        assert_eq!(
+            Leb128::<u16>::try_from_slice(&[0xFF, 0xFF, 0x03])
+                .unwrap()
+                .into_number(),
+            u16::MAX
+        );
+        assert!(Leb128::<u64>::try_from_slice(&[0x80; 11]).is_err());
+    }
+
+    #[test]
+    fn over_long_test() {
+        assert!(Leb128::<u64>::try_from_slice(&[0x80, 0x00]).is_err());
+        assert!(Leb128::<u64>::try_from_slice(&[0x81, 0x80, 0x00]).is_err());
+        assert!(Leb128::<i64>::try_from_slice(&[0x80, 0x00]).is_err());
+        assert_eq!(Leb128::<u64>::try_from_slice(&[0x00]).unwrap().into_number(), 0);
+        assert_eq!(
+            Leb128::<u64>::try_from_slice(&[0x80, 0x01])
+                .unwrap()
+                .into_number(),
+            128
+        );
+    }
+}
@@ -9,9 +9,11 @@ use std::num::{
 use borsh::{BorshDeserialize, BorshSerialize};
 
 pub use byte_count::*;
+pub use leb128::*;
 pub use zero_count::*;
 
 mod byte_count;
+mod leb128;
 mod zero_count;
 
 /// Represents a u64 that is compressed and decompressed on reading/writing from/to bytes
@@ -21,9 +23,9 @@ mod zero_count;
 pub unsafe trait CompressedNumber: Copy + BorshSerialize + BorshDeserialize + Eq {
     /// The number that is represented
     type Num;
-    /// This function is required to have a const version. Will be added when const functions are in traits.
+    /// Compresses `number`. Compression at compile time is done with [`ConstCompressedNumber`].
     fn from_number(number: Self::Num) -> Self;
-    /// This function is required to have a const version. Will be added when const functions are in traits.
+    /// Decompresses this number.
     fn into_number(self) -> Self::Num;
     /// The number of bytes the compressed version will take
     fn num_bytes(self) -> usize;
@@ -31,6 +33,90 @@ pub unsafe trait CompressedNumber: Copy + BorshSerialize + BorshDeserialize + Eq
     fn max_bytes() -> usize;
 }
 
+/// The max number of bytes in [`ConstBytes`]
+pub const MAX_CONST_BYTES: usize = 16;
+
+/// Compressed bytes of a number evaluated at compile time by [`ConstCompressedNumber`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConstBytes {
+    bytes: [u8; MAX_CONST_BYTES],
+    num_bytes: usize,
+}
+impl ConstBytes {
+    /// Creates new bytes from the first `num_bytes` of `bytes`
+    ///
+    /// # Panics
+    /// Panics if `num_bytes` is greater than [`MAX_CONST_BYTES`]
+    #[must_use]
+    pub const fn new(bytes: [u8; MAX_CONST_BYTES], num_bytes: usize) -> Self {
+        assert!(num_bytes <= MAX_CONST_BYTES, "Too many bytes");
+        Self { bytes, num_bytes }
+    }
+
+    /// Creates new bytes from `bytes`
+    ///
+    /// # Panics
+    /// Panics if `N` is greater than [`MAX_CONST_BYTES`]
+    #[must_use]
+    pub const fn from_array<const N: usize>(bytes: [u8; N]) -> Self {
+        assert!(N <= MAX_CONST_BYTES, "Too many bytes");
+        let mut out = [0; MAX_CONST_BYTES];
+        let mut index = 0;
+        while index < N {
+            out[index] = bytes[index];
+            index += 1;
+        }
+        Self::new(out, N)
+    }
+
+    /// The number of bytes used
+    #[must_use]
+    pub const fn num_bytes(self) -> usize {
+        self.num_bytes
+    }
+
+    /// Turns the used bytes into an array, `N` must be [`ConstBytes::num_bytes`]
+    ///
+    /// # Panics
+    /// Panics if `N` is not [`ConstBytes::num_bytes`]
+    #[must_use]
+    pub const fn to_array<const N: usize>(self) -> [u8; N] {
+        assert!(N == self.num_bytes, "Array length must equal `num_bytes`");
+        let mut out = [0; N];
+        let mut index = 0;
+        while index < N {
+            out[index] = self.bytes[index];
+            index += 1;
+        }
+        out
+    }
+
+    /// The used bytes
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.num_bytes]
+    }
+}
+
+/// A [`CompressedNumber`] that can compress the number `N` at compile time.
+/// Used for the discriminant bytes of [`AccountListItem`](crate::account_list::AccountListItem) and [`InstructionList`](crate::instruction_list::InstructionList).
+///
+/// # Safety
+/// [`ConstCompressedNumber::BYTES`] must equal the serialization of `N` by [`CompressedNumber`]
+pub unsafe trait ConstCompressedNumber<const N: u64>: CompressedNumber {
+    /// `N` compressed
+    const BYTES: ConstBytes;
+}
+unsafe impl<const N: u64> ConstCompressedNumber<N> for u64 {
+    const BYTES: ConstBytes = ConstBytes::from_array(N.to_le_bytes());
+}
+unsafe impl<const N: u64> ConstCompressedNumber<N> for NonZeroU64 {
+    const BYTES: ConstBytes = {
+        assert!(N != 0, "Zero value for non-zero number");
+        ConstBytes::from_array(N.to_le_bytes())
+    };
+}
+
 macro_rules! impl_compressed_for_prim {
     (all: $($ty:ty),+ $(,)?) => {
         $(impl_compressed_for_prim!($ty);)+
@@ -81,3 +167,58 @@ impl_compressed_for_prim!(
     NonZeroI64,
     NonZeroI128,
 );
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn const_test<T, const N: u64>(number: T::Num)
+    where
+        T: ConstCompressedNumber<N>,
+        T::Num: Copy,
+    {
+        assert_eq!(
+            T::BYTES.as_slice(),
+            T::from_number(number).try_to_vec().unwrap(),
+            "Number: {}",
+            N
+        );
+        assert_eq!(T::BYTES.num_bytes(), T::from_number(number).num_bytes());
+    }
+
+    macro_rules! const_tests {
+        ($($number:expr),+ $(,)?) => {
+            $(
+                const_test::<u64, { $number }>($number);
+                const_test::<NonZeroU64, { $number }>(NonZeroU64::new($number).unwrap());
+                const_test::<ByteCount<u64>, { $number }>($number);
+                const_test::<ZeroCount<u64>, { $number }>($number);
+                const_test::<ZeroCount<NonZeroU64>, { $number }>(NonZeroU64::new($number).unwrap());
+                const_test::<Leb128<u64>, { $number }>($number);
+                const_test::<Leb128<NonZeroU64>, { $number }>(NonZeroU64::new($number).unwrap());
+            )+
+        };
+    }
+
+    #[test]
+    fn const_compressed_test() {
+        const_tests!(
+            1,
+            127,
+            128,
+            255,
+            256,
+            16383,
+            16384,
+            0xFFFF_FFFF,
+            0x00FF_FFFF_FFFF_FFFF,
+            0x0100_0000_0000_0000,
+            u64::MAX,
+        );
+        const_test::<u64, 0>(0);
+        const_test::<ByteCount<u64>, 0>(0);
+        const_test::<ZeroCount<u64>, 0>(0);
+        const_test::<Leb128<u64>, 0>(0);
+        assert_eq!(ConstBytes::from_array([1, 2, 3]).to_array::<3>(), [1, 2, 3]);
+    }
+}
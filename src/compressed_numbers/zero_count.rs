@@ -1,96 +1,240 @@
-// use crate::compressed_numbers::CompressedU64;
-// use borsh::{BorshDeserialize, BorshSerialize};
-// use cruiser::bytes_ext::ReadExt;
-// use solana_program::program_memory::sol_memcpy;
-// use std::io::{Read, Write};
-// use std::mem::size_of;
-//
-// #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
-// pub struct ZeroCount<T>(T);
-// impl ZeroCount<u64> {
-//     const BYTES_NEEDED_LOOKUP_TABLE: &'static [u8] = &[
-//         9, 9, 9, 9, 9, 9, 9, 9, 8, 8, 8, 8, 8, 8, 8, 7, 7, 7, 7, 7, 7, 7, 6, 6, 6, 6, 6, 6, 6, 5,
-//         5, 5, 5, 5, 5, 5, 4, 4, 4, 4, 4, 4, 4, 3, 3, 3, 3, 3, 3, 3, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1,
-//         1, 1, 1, 1, 1,
-//     ];
-//     const MAX_BYTES_NEEDED: u8 = {
-//         let mut max = u8::MIN;
-//         let mut index = 0;
-//         loop {
-//             if index >= Self::BYTES_NEEDED_LOOKUP_TABLE.len() {
-//                 break;
-//             }
-//             if Self::BYTES_NEEDED_LOOKUP_TABLE[index] > max {
-//                 max = Self::BYTES_NEEDED_LOOKUP_TABLE[index];
-//             }
-//             index += 1;
-//         }
-//         max
-//     };
-//
-//     const fn from_u64(number: u64) -> Self {
-//         Self(number)
-//     }
-//
-//     const fn into_u64(self) -> u64 {
-//         self.0
-//     }
-// }
-// unsafe impl CompressedU64 for ZeroCount<u64> {
-//     fn from_u64(number: u64) -> Self {
-//         Self::from_u64(number)
-//     }
-//
-//     fn into_u64(self) -> u64 {
-//         self.into_u64()
-//     }
-//
-//     fn num_bytes(self) -> usize {
-//         todo!()
-//     }
-// }
-// impl BorshSerialize for ZeroCount<u64> {
-//     fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-//         let zeros = self.0.leading_zeros() as usize;
-//         let bytes_needed = Self::BYTES_NEEDED_LOOKUP_TABLE[zeros] as usize;
-//         let mut bytes = [0; Self::MAX_BYTES_NEEDED as usize];
-//         sol_memcpy(&mut bytes[1..], &self.0.to_be_bytes(), size_of::<u64>());
-//         let start_byte = bytes.len() - bytes_needed;
-//         bytes[start_byte] |= (1 << 7) >> (bytes_needed - 1);
-//         writer.write_all(&bytes[start_byte..])
-//     }
-// }
-// impl BorshDeserialize for ZeroCount<u64> {
-//     fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
-//         let first_byte = buf.read_u8()?;
-//         let leading_zeros = first_byte.leading_zeros() as usize;
-//         let mut bytes = [0; size_of::<u64>()];
-//         let write_offset = if leading_zeros != 8 {
-//             let write_offset = 7 - leading_zeros;
-//             bytes[write_offset] = first_byte & !(1 << write_offset);
-//             write_offset + 1
-//         } else {
-//             0
-//         };
-//         buf.read_exact(&mut bytes[write_offset..])?;
-//         Ok(Self(u64::from_le_bytes(bytes)))
-//     }
-// }
-//
-// #[cfg(test)]
-// mod test {
-//     use super::*;
-//     use rand::{thread_rng, Rng};
-//     #[test]
-//     fn serde_test() {
-//         let mut rng = thread_rng();
-//         for index in 0..u64::BITS as usize {
-//             let val =
-//                 ((rng.gen::<u64>() << index) >> index) & (1 << (size_of::<u64>() - index - 1));
-//             let before = ZeroCount::from_u64(val);
-//             let data = before.try_to_vec().unwrap();
-//             let after = ZeroCount::try_from_slice(&data).unwrap();
-//             assert_eq!(before, after);
-//         }
-//     }
-// }
+use crate::compressed_numbers::{
+    CompressedNumber, ConstBytes, ConstCompressedNumber, MAX_CONST_BYTES,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use cruiser::util::bytes_ext::ReadExt;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::mem::size_of;
+use std::num::NonZeroU64;
+
+/// A compressed number whose count of leading zero bits in the first byte is the number of following bytes.
+/// Takes 1 byte for numbers under `2^7` up to 9 bytes for numbers of `2^56` and above.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+pub struct ZeroCount<T>(T);
+impl ZeroCount<u64> {
+    const fn from_u64(number: u64) -> Self {
+        Self(number)
+    }
+
+    const fn into_u64(self) -> u64 {
+        self.0
+    }
+
+    const fn num_bytes(self) -> usize {
+        zero_count_bytes(self.0)
+    }
+}
+impl ZeroCount<NonZeroU64> {
+    const fn from_non_zero(number: NonZeroU64) -> Self {
+        Self(number)
+    }
+
+    const fn into_non_zero(self) -> NonZeroU64 {
+        self.0
+    }
+
+    const fn num_bytes(self) -> usize {
+        zero_count_bytes(self.0.get())
+    }
+}
+
+/// The max number of bytes a [`ZeroCount`] takes
+const MAX_BYTES: usize = size_of::<u64>() + 1;
+
+const fn zero_count_bytes(number: u64) -> usize {
+    let bits = (u64::BITS - number.leading_zeros()) as usize;
+    if bits <= 7 {
+        1
+    } else if bits > 7 * size_of::<u64>() {
+        MAX_BYTES
+    } else {
+        (bits + 6) / 7
+    }
+}
+
+const fn zero_count_const(number: u64) -> ConstBytes {
+    let bytes_needed = zero_count_bytes(number);
+    let bytes = number.to_be_bytes();
+    let mut out = [0; MAX_CONST_BYTES];
+    // The max size has a zero first byte before all the bytes of the number
+    let lead = if bytes_needed == MAX_BYTES { 1 } else { 0 };
+    let start = size_of::<u64>() + lead - bytes_needed;
+    let mut index = start;
+    while index < size_of::<u64>() {
+        out[index - start + lead] = bytes[index];
+        index += 1;
+    }
+    if lead == 0 {
+        out[0] |= (1 << 7) >> (bytes_needed - 1);
+    }
+    ConstBytes::new(out, bytes_needed)
+}
+
+fn write_zero_count<W: Write>(number: u64, writer: &mut W) -> std::io::Result<()> {
+    writer.write_all(zero_count_const(number).as_slice())
+}
+
+fn read_zero_count(buf: &mut &[u8]) -> std::io::Result<u64> {
+    let first = buf.read_u8()?;
+    let leading_zeros = first.leading_zeros() as usize;
+    let mut bytes = [0; size_of::<u64>()];
+    if leading_zeros == 8 {
+        buf.read_exact(&mut bytes)?;
+    } else {
+        let start = size_of::<u64>() - leading_zeros - 1;
+        bytes[start] = first & (((1 << 7) >> leading_zeros) - 1);
+        buf.read_exact(&mut bytes[start + 1..])?;
+    }
+    let number = u64::from_be_bytes(bytes);
+    if zero_count_bytes(number) == (leading_zeros + 1).min(MAX_BYTES) {
+        Ok(number)
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            "ZeroCount number is not minimally encoded",
+        ))
+    }
+}
+
+unsafe impl CompressedNumber for ZeroCount<u64> {
+    type Num = u64;
+
+    #[inline]
+    fn from_number(number: Self::Num) -> Self {
+        Self::from_u64(number)
+    }
+
+    #[inline]
+    fn into_number(self) -> Self::Num {
+        self.into_u64()
+    }
+
+    #[inline]
+    fn num_bytes(self) -> usize {
+        self.num_bytes()
+    }
+
+    #[inline]
+    fn max_bytes() -> usize {
+        MAX_BYTES
+    }
+}
+unsafe impl<const N: u64> ConstCompressedNumber<N> for ZeroCount<u64> {
+    const BYTES: ConstBytes = zero_count_const(N);
+}
+impl BorshSerialize for ZeroCount<u64> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_zero_count(self.0, writer)
+    }
+}
+impl BorshDeserialize for ZeroCount<u64> {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        read_zero_count(buf).map(Self)
+    }
+}
+
+unsafe impl CompressedNumber for ZeroCount<NonZeroU64> {
+    type Num = NonZeroU64;
+
+    #[inline]
+    fn from_number(number: Self::Num) -> Self {
+        Self::from_non_zero(number)
+    }
+
+    #[inline]
+    fn into_number(self) -> Self::Num {
+        self.into_non_zero()
+    }
+
+    #[inline]
+    fn num_bytes(self) -> usize {
+        self.num_bytes()
+    }
+
+    #[inline]
+    fn max_bytes() -> usize {
+        MAX_BYTES
+    }
+}
+unsafe impl<const N: u64> ConstCompressedNumber<N> for ZeroCount<NonZeroU64> {
+    const BYTES: ConstBytes = {
+        assert!(N != 0, "Zero value for non-zero number");
+        zero_count_const(N)
+    };
+}
+impl BorshSerialize for ZeroCount<NonZeroU64> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_zero_count(self.0.get(), writer)
+    }
+}
+impl BorshDeserialize for ZeroCount<NonZeroU64> {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        NonZeroU64::new(read_zero_count(buf)?)
+            .map(Self)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Zero value for non-zero number"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn random_test() {
+        let mut rng = thread_rng();
+        for _ in 0..1 << 12 {
+            for bits in 0..=u64::BITS {
+                let val = rng.gen::<u64>().checked_shr(u64::BITS - bits).unwrap_or(0);
+                let before = ZeroCount::from_u64(val);
+                let bytes = before.try_to_vec().unwrap();
+                assert_eq!(bytes.len(), before.num_bytes(), "Value: {}", val);
+                let after = ZeroCount::try_from_slice(&bytes).unwrap_or_else(|error| {
+                    panic!(
+                        "Error encountered: {}\n number: {:?}, bytes: {:?}",
+                        error, before, bytes
+                    )
+                });
+                assert_eq!(before, after, "Bytes: {:?}", bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn non_zero_test() {
+        let mut rng = thread_rng();
+        for _ in 0..1 << 16 {
+            let val =
+                NonZeroU64::new((rng.gen::<u64>() >> rng.gen_range(0, u64::BITS)).max(1)).unwrap();
+            let before = ZeroCount::from_non_zero(val);
+            let bytes = before.try_to_vec().unwrap();
+            assert_eq!(bytes.len(), before.num_bytes());
+            assert_eq!(before, ZeroCount::try_from_slice(&bytes).unwrap());
+        }
+        assert!(ZeroCount::<NonZeroU64>::try_from_slice(&[1 << 7]).is_err());
+    }
+
+    #[test]
+    fn boundary_test() {
+        for bytes_needed in 1..=size_of::<u64>() {
+            let max = (1 << (7 * bytes_needed)) - 1;
+            assert_eq!(ZeroCount::from_u64(max).num_bytes(), bytes_needed);
+            assert_eq!(ZeroCount::from_u64(max + 1).num_bytes(), bytes_needed + 1);
+        }
+        assert_eq!(ZeroCount::from_u64(u64::MAX).num_bytes(), MAX_BYTES);
+        assert_eq!(ZeroCount::from_u64(0).try_to_vec().unwrap(), vec![1 << 7]);
+    }
+
+    #[test]
+    fn over_long_test() {
+        assert!(ZeroCount::<u64>::try_from_slice(&[1 << 6, 0x01]).is_err());
+        assert!(ZeroCount::<u64>::try_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0xFF]).is_err());
+        assert!(ZeroCount::<NonZeroU64>::try_from_slice(&[1 << 6, 0x01]).is_err());
+        assert_eq!(
+            ZeroCount::<u64>::try_from_slice(&[1 << 6 | 0x01, 0x00])
+                .unwrap()
+                .into_u64(),
+            1 << 8
+        );
+    }
+}
@@ -239,4 +239,89 @@ pub enum GenericError {
         /// The maximum new data length
         max_new_len: usize,
     },
+    /// Program has no upgrade authority
+    #[error_msg("Program with program data `{}` is not upgradeable", program_data)]
+    ProgramNotUpgradeable {
+        /// The program data account of the program
+        program_data: Pubkey,
+    },
+    /// Instruction was invoked through CPI when it must be top level
+    #[error_msg(
+        "Instruction of program `{}` must not be called through CPI",
+        program_id
+    )]
+    CalledThroughCPI {
+        /// The program that was called through CPI
+        program_id: Pubkey,
+    },
+    /// No signature verification instruction was found in the transaction
+    #[error_msg(
+        "No signature verification instruction found for signer `{}` and the expected message",
+        signer
+    )]
+    MissingSignatureVerification {
+        /// The signer that was expected
+        signer: String,
+    },
+    /// Nonce of a signed message did not match
+    #[error_msg("Invalid nonce `{}`, expected `{}`", received, expected)]
+    InvalidNonce {
+        /// The nonce that was expected
+        expected: u64,
+        /// The nonce that was received
+        received: u64,
+    },
+    /// A token account or mint did not pass a validation check
+    #[error_msg(
+        "Token check `{}` failed for account `{}`. Expected: `{}`, Received: `{}`",
+        check,
+        account,
+        expected,
+        received
+    )]
+    TokenCheckFailed {
+        /// The token account or mint that failed the check
+        account: Pubkey,
+        /// The check that failed
+        check: String,
+        /// The expected value
+        expected: String,
+        /// The value of the account
+        received: String,
+    },
+    /// Not enough valid signers signed for a multisig
+    #[error_msg(
+        "Multisig `{}` needs `{}` signers, only `{}` valid signers signed",
+        multisig,
+        needed,
+        signed
+    )]
+    NotEnoughMultisigSigners {
+        /// The multisig account
+        multisig: Pubkey,
+        /// The number of signers needed
+        needed: u8,
+        /// The number of valid signers that signed
+        signed: usize,
+    },
+    /// A PDA account was given no seeds when they are needed
+    #[error_msg("No seeds given for PDA account `{}`", account)]
+    MissingAccountSeeds {
+        /// The account missing seeds
+        account: Pubkey,
+    },
+    /// A program account was not executable
+    #[error_msg("Program account `{}` is not executable", account)]
+    AccountNotExecutable {
+        /// The account that is not executable
+        account: Pubkey,
+    },
+    /// No account type in the account list has the account's discriminant
+    #[error_msg("Account `{}` has unknown discriminant `{}`", account, discriminant)]
+    UnknownAccountDiscriminant {
+        /// The account with the unknown discriminant
+        account: Pubkey,
+        /// The discriminant of the account
+        discriminant: u64,
+    },
 }
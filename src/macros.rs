@@ -91,3 +91,26 @@ macro_rules! impl_account_info {
         }
     };
 }
+
+/// Declares a unit type implementing [`ProgramKey`](crate::program::ProgramKey) for an arbitrary program address.
+/// Use it with [`ProgramAccount`](crate::account_types::program_account::ProgramAccount) to call programs without their own wrapper.
+///
+/// ```ignore
+/// use cruiser::{declare_program, solana_program::pubkey};
+///
+/// declare_program!(
+///     /// The SPL memo program
+///     pub MemoProgram = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr")
+/// );
+/// ```
+#[macro_export]
+macro_rules! declare_program {
+    ($(#[$meta:meta])* $vis:vis $name:ident = $key:expr $(,)?) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        $vis struct $name;
+        impl $crate::program::ProgramKey for $name {
+            const KEY: $crate::Pubkey = $key;
+        }
+    };
+}
@@ -1,17 +1,45 @@
 //! Support for type based PDAs
 use std::fmt::Debug;
 use std::iter::{once, Chain, Map, Once};
+use std::ops::Deref;
 
 use crate::cpi::CPI;
 use crate::solana_program::entrypoint::ProgramResult;
-use crate::solana_program::pubkey::PubkeyError;
+use crate::solana_program::program_error::ProgramError;
+use crate::solana_program::pubkey::{PubkeyError, MAX_SEEDS};
 use crate::{CruiserResult, GenericError, Pubkey, SolanaInstruction, ToSolanaAccountInfo};
 
+pub use cruiser_derive::PDASeeder;
+
+/// The seeds of a PDA written to the stack, sized for the maximum seeds including the bump seed.
+pub type SeedBuffer<'a> = [&'a [u8]; MAX_SEEDS];
+
+/// The number of seed sets [`PDASeedSet::invoke_signed_multiple`] can sign with before allocating.
+pub const MAX_SIGNER_SEED_SETS: usize = 4;
+
+/// The seeder of a [`PDASeedSet`]. Borrowing the seeder avoids a heap allocation.
+#[derive(Debug)]
+enum SetSeeder<'a> {
+    /// A borrowed seeder
+    Borrowed(&'a dyn PDASeeder),
+    /// A boxed seeder
+    Boxed(Box<dyn PDASeeder + 'a>),
+}
+impl<'a> Deref for SetSeeder<'a> {
+    type Target = dyn PDASeeder + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            SetSeeder::Borrowed(seeder) => *seeder,
+            SetSeeder::Boxed(seeder) => &**seeder,
+        }
+    }
+}
+
 /// A set of seeds for a pda
 #[derive(Debug)]
 pub struct PDASeedSet<'a> {
-    /// The seeder for these seeds
-    pub seeder: Box<dyn PDASeeder + 'a>,
+    seeder: SetSeeder<'a>,
     /// The nonce of the account
     pub nonce: [u8; 1],
 }
@@ -22,20 +50,67 @@ impl<'a> PDASeedSet<'a> {
     }
 
     /// Finds a set of pda seeds
+    ///
+    /// # Panics
+    /// Panics like [`PDAGenerator::find_address`], use [`PDASeedSet::try_find`] to handle the error.
+    #[must_use]
     pub fn find(seeder: impl PDASeeder + 'a, program_id: &Pubkey) -> (Pubkey, Self) {
         let (key, bump) = seeder.find_address(program_id);
         (key, Self::from_boxed(Box::new(seeder), bump))
     }
 
+    /// Finds a set of pda seeds, erroring instead of panicking like [`PDASeedSet::find`]
+    pub fn try_find(
+        seeder: impl PDASeeder + 'a,
+        program_id: &Pubkey,
+    ) -> CruiserResult<(Pubkey, Self)> {
+        let (key, bump) = seeder.try_find_address(program_id)?;
+        Ok((key, Self::from_boxed(Box::new(seeder), bump)))
+    }
+
     /// Creates a new set of seeds from an already boxed seeder
     #[must_use]
     pub fn from_boxed(seeder: Box<dyn PDASeeder + 'a>, nonce: u8) -> Self {
         PDASeedSet {
-            seeder,
+            seeder: SetSeeder::Boxed(seeder),
+            nonce: [nonce],
+        }
+    }
+
+    /// Creates a new set of seeds borrowing the seeder, does not allocate
+    #[must_use]
+    pub fn borrowed(seeder: &'a dyn PDASeeder, nonce: u8) -> Self {
+        PDASeedSet {
+            seeder: SetSeeder::Borrowed(seeder),
             nonce: [nonce],
         }
     }
 
+    /// Finds a set of pda seeds borrowing the seeder
+    ///
+    /// # Panics
+    /// Panics like [`PDAGenerator::find_address`], use [`PDASeedSet::try_find_borrowed`] to handle the error.
+    #[must_use]
+    pub fn find_borrowed(seeder: &'a dyn PDASeeder, program_id: &Pubkey) -> (Pubkey, Self) {
+        let (key, bump) = seeder.find_address(program_id);
+        (key, Self::borrowed(seeder, bump))
+    }
+
+    /// Finds a set of pda seeds borrowing the seeder, erroring instead of panicking like [`PDASeedSet::find_borrowed`]
+    pub fn try_find_borrowed(
+        seeder: &'a dyn PDASeeder,
+        program_id: &Pubkey,
+    ) -> CruiserResult<(Pubkey, Self)> {
+        let (key, bump) = seeder.try_find_address(program_id)?;
+        Ok((key, Self::borrowed(seeder, bump)))
+    }
+
+    /// The seeder for these seeds
+    #[must_use]
+    pub fn seeder(&self) -> &(dyn PDASeeder + 'a) {
+        &*self.seeder
+    }
+
     /// Verifies that a given address is derived from this seed set.
     pub fn verify_address(&self, program_id: &Pubkey, address: &Pubkey) -> CruiserResult<()> {
         self.seeder
@@ -52,6 +127,13 @@ impl<'a> PDASeedSet<'a> {
         self.seeder.seeds().chain(once(&self.nonce as &dyn PDASeed))
     }
 
+    /// Writes the seeds including the nonce to `buffer` returning how many were written
+    pub fn write_signer_seeds<'b>(&'b self, buffer: &mut SeedBuffer<'b>) -> CruiserResult<usize> {
+        let count = self.seeder.write_seed_bytes(buffer)?;
+        buffer[count] = &self.nonce;
+        Ok(count + 1)
+    }
+
     /// Invokes an instruction with these seeds
     pub fn invoke_signed<'b, AI: ToSolanaAccountInfo<'b>, const N: usize>(
         &self,
@@ -59,9 +141,12 @@ impl<'a> PDASeedSet<'a> {
         instruction: &SolanaInstruction,
         account_infos: &[&AI; N],
     ) -> ProgramResult {
-        let seeds = self.seeds().map(AsRef::as_ref).collect::<Vec<_>>();
+        let mut buffer = [&[] as &[u8]; MAX_SEEDS];
+        let count = self
+            .write_signer_seeds(&mut buffer)
+            .map_err(|error| error.to_program_error())?;
 
-        cpi.invoke_signed(instruction, account_infos, &[&seeds])
+        cpi.invoke_signed(instruction, account_infos, &[&buffer[..count]])
     }
 
     /// Invokes an instruction of variable account size with these seeds
@@ -71,28 +156,29 @@ impl<'a> PDASeedSet<'a> {
         instruction: &SolanaInstruction,
         account_infos: impl IntoIterator<Item = &'b AI>,
     ) -> ProgramResult {
-        let seeds = self.seeds().map(AsRef::as_ref).collect::<Vec<_>>();
+        let mut buffer = [&[] as &[u8]; MAX_SEEDS];
+        let count = self
+            .write_signer_seeds(&mut buffer)
+            .map_err(|error| error.to_program_error())?;
 
-        cpi.invoke_signed_variable_size(instruction, account_infos, &[&seeds])
+        cpi.invoke_signed_variable_size(instruction, account_infos, &[&buffer[..count]])
     }
 
-    /// Invokes an instruction with given seed sets
+    /// Invokes an instruction with given seed sets.
+    /// Does not allocate for up to [`MAX_SIGNER_SEED_SETS`] seed sets, more seed sets are written to the heap.
     pub fn invoke_signed_multiple<'b: 'a, 'c, AI: ToSolanaAccountInfo<'c>, const N: usize>(
         cpi: impl CPI,
         instruction: &SolanaInstruction,
         account_infos: &[&AI; N],
         seed_sets: impl IntoIterator<Item = &'a PDASeedSet<'b>>,
     ) -> ProgramResult {
-        let seeds_array = seed_sets
-            .into_iter()
-            .map(|seed_set| seed_set.seeds().map(AsRef::as_ref).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let seeds = seeds_array.iter().map(AsRef::as_ref).collect::<Vec<_>>();
-
-        cpi.invoke_signed(instruction, account_infos, seeds.as_slice())
+        Self::with_signer_seeds(seed_sets, |signer_seeds| {
+            cpi.invoke_signed(instruction, account_infos, signer_seeds)
+        })?
     }
 
-    /// Invokes an instruction of variable account size with given seed sets
+    /// Invokes an instruction of variable account size with given seed sets.
+    /// Does not allocate for up to [`MAX_SIGNER_SEED_SETS`] seed sets, more seed sets are written to the heap.
     pub fn invoke_signed_variable_size_multiple<
         'b: 'a,
         'c,
@@ -104,13 +190,66 @@ impl<'a> PDASeedSet<'a> {
         account_infos: impl IntoIterator<Item = &'c AI>,
         seed_sets: impl IntoIterator<Item = &'a PDASeedSet<'b>>,
     ) -> ProgramResult {
-        let seeds_array = seed_sets
-            .into_iter()
-            .map(|seed_set| seed_set.seeds().map(AsRef::as_ref).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let seeds = seeds_array.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+        Self::with_signer_seeds(seed_sets, |signer_seeds| {
+            cpi.invoke_signed_variable_size(instruction, account_infos, signer_seeds)
+        })?
+    }
+
+    /// Writes the signer seeds of `seed_sets` and calls `function` with them.
+    /// Uses stack buffers for up to [`MAX_SIGNER_SEED_SETS`] seed sets and falls back to the heap for more.
+    fn with_signer_seeds<'b: 'a, R>(
+        seed_sets: impl IntoIterator<Item = &'a PDASeedSet<'b>>,
+        function: impl FnOnce(&[&[&[u8]]]) -> R,
+    ) -> Result<R, ProgramError> {
+        let mut seed_sets = seed_sets.into_iter();
+        let mut stack_sets: [Option<&'a PDASeedSet<'b>>; MAX_SIGNER_SEED_SETS] =
+            [None; MAX_SIGNER_SEED_SETS];
+        let mut count = 0;
+        while let Some(seed_set) = seed_sets.next() {
+            if count == MAX_SIGNER_SEED_SETS {
+                let buffers = stack_sets
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .chain(once(seed_set))
+                    .chain(seed_sets)
+                    .map(|seed_set| {
+                        let mut buffer = [&[] as &[u8]; MAX_SEEDS];
+                        let seeds = seed_set
+                            .write_signer_seeds(&mut buffer)
+                            .map_err(|error| error.to_program_error())?;
+                        Ok((buffer, seeds))
+                    })
+                    .collect::<Result<Vec<_>, ProgramError>>()?;
+                let signer_seeds = buffers
+                    .iter()
+                    .map(|(buffer, seeds)| &buffer[..*seeds])
+                    .collect::<Vec<_>>();
+                return Ok(function(&signer_seeds));
+            }
+            stack_sets[count] = Some(seed_set);
+            count += 1;
+        }
 
-        cpi.invoke_signed_variable_size(instruction, account_infos, seeds.as_slice())
+        let mut buffers = [[&[] as &[u8]; MAX_SEEDS]; MAX_SIGNER_SEED_SETS];
+        let mut seed_counts = [0; MAX_SIGNER_SEED_SETS];
+        for ((seed_set, buffer), seeds) in stack_sets
+            .iter()
+            .flatten()
+            .zip(buffers.iter_mut())
+            .zip(seed_counts.iter_mut())
+        {
+            *seeds = seed_set
+                .write_signer_seeds(buffer)
+                .map_err(|error| error.to_program_error())?;
+        }
+        let mut signer_seeds = [&[] as &[&[u8]]; MAX_SIGNER_SEED_SETS];
+        for ((signer_seeds, buffer), seeds) in
+            signer_seeds.iter_mut().zip(buffers.iter()).zip(seed_counts)
+        {
+            *signer_seeds = &buffer[..seeds];
+        }
+        Ok(function(&signer_seeds[..count]))
     }
 }
 impl<'a> AsRef<PDASeedSet<'a>> for PDASeedSet<'a> {
@@ -154,11 +293,38 @@ impl PDASeed for Vec<u8> {
         format!("{:?}", self)
     }
 }
+impl<T: ?Sized> PDASeed for &T
+where
+    T: PDASeed,
+{
+    fn to_seed_string(&self) -> String {
+        T::to_seed_string(self)
+    }
+}
 
 /// A set of seeds for a given PDA type.
+///
+/// Seeding only avoids heap allocation for seeders that override [`PDASeeder::write_seed_bytes`],
+/// such as those using [`derive@PDASeeder`] or implementing [`StaticPDASeeder`] and using [`write_static_seed_bytes`].
+/// Other seeders allocate the boxed iterator of [`PDASeeder::seeds`] whenever their seeds are used.
 pub trait PDASeeder: Debug {
     /// Gets an iterator of seeds for this address.
     fn seeds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn PDASeed> + 'a>;
+
+    /// Writes the seed bytes to `buffer` returning how many were written, leaving room for the bump seed.
+    /// Defaults to iterating [`PDASeeder::seeds`] which allocates the boxed iterator,
+    /// hand written seeders should implement [`StaticPDASeeder`] and override this with [`write_static_seed_bytes`] to avoid it.
+    fn write_seed_bytes<'a>(&'a self, buffer: &mut SeedBuffer<'a>) -> CruiserResult<usize> {
+        let mut count = 0;
+        for seed in self.seeds() {
+            if count >= MAX_SEEDS - 1 {
+                return Err(PubkeyError::MaxSeedLengthExceeded.into());
+            }
+            buffer[count] = seed.as_ref();
+            count += 1;
+        }
+        Ok(count)
+    }
 }
 impl<'b, T: ?Sized> PDASeeder for &'b T
 where
@@ -167,6 +333,10 @@ where
     fn seeds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn PDASeed> + 'a> {
         T::seeds(self)
     }
+
+    fn write_seed_bytes<'a>(&'a self, buffer: &mut SeedBuffer<'a>) -> CruiserResult<usize> {
+        T::write_seed_bytes(self, buffer)
+    }
 }
 impl<'b, T: ?Sized> PDASeeder for &'b mut T
 where
@@ -175,6 +345,10 @@ where
     fn seeds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn PDASeed> + 'a> {
         T::seeds(self)
     }
+
+    fn write_seed_bytes<'a>(&'a self, buffer: &mut SeedBuffer<'a>) -> CruiserResult<usize> {
+        T::write_seed_bytes(self, buffer)
+    }
 }
 impl<T: ?Sized> PDASeeder for Box<T>
 where
@@ -183,6 +357,55 @@ where
     fn seeds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn PDASeed> + 'a> {
         T::seeds(self)
     }
+
+    fn write_seed_bytes<'a>(&'a self, buffer: &mut SeedBuffer<'a>) -> CruiserResult<usize> {
+        T::write_seed_bytes(self, buffer)
+    }
+}
+
+/// A [`PDASeeder`] with a fixed number of seeds `N` that can be used without heap allocation.
+/// Implemented by [`derive@PDASeeder`].
+pub trait StaticPDASeeder<const N: usize>: PDASeeder {
+    /// Gets the bytes of the seeds, not including the bump seed
+    fn seed_bytes(&self) -> [&[u8]; N];
+}
+
+/// Writes the seeds of a [`StaticPDASeeder`] to `buffer`, for implementing [`PDASeeder::write_seed_bytes`] without allocating.
+pub fn write_static_seed_bytes<'a, S, const N: usize>(
+    seeder: &'a S,
+    buffer: &mut SeedBuffer<'a>,
+) -> CruiserResult<usize>
+where
+    S: StaticPDASeeder<N> + ?Sized,
+{
+    if N >= MAX_SEEDS {
+        return Err(PubkeyError::MaxSeedLengthExceeded.into());
+    }
+    buffer[..N].copy_from_slice(&seeder.seed_bytes());
+    Ok(N)
+}
+
+/// A seed in the layout described by [`PDASeederIdl`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IdlSeed {
+    /// A literal seed
+    Const {
+        /// The value of the seed
+        value: &'static str,
+    },
+    /// A seed from a field of the seeder
+    Field {
+        /// The name of the field
+        name: &'static str,
+        /// The type of the field
+        ty: &'static str,
+    },
+}
+
+/// Describes the seed layout of a [`PDASeeder`] for tooling, implemented by [`derive@PDASeeder`].
+pub trait PDASeederIdl: PDASeeder {
+    /// The seeds in order, not including the bump seed
+    const SEEDS: &'static [IdlSeed];
 }
 
 /// Generates a PDA from a given seeder.
@@ -208,8 +431,15 @@ where
     fn seeds_to_strings(&'a self) -> Self::SeedsToStringsIter;
     /// Gets the seeds as an iterator of strings with an additional nonce
     fn seeds_to_strings_with_nonce(&'a self, nonce: u8) -> Self::SeedsToStringsWithNonceIter;
-    /// Finds an address for the given seeds returning `(key, nonce)`
+    /// Finds an address for the given seeds returning `(key, nonce)`.
+    ///
+    /// # Panics
+    /// Panics if there are more than [`MAX_SEEDS`] seeds including the bump seed or no bump seed is valid,
+    /// use [`PDAGenerator::try_find_address`] to handle the error.
     fn find_address(&self, program_id: &Pubkey) -> (Pubkey, u8);
+    /// Finds an address for the given seeds returning `(key, nonce)`.
+    /// Errors if there are more than [`MAX_SEEDS`] seeds including the bump seed or no bump seed is valid.
+    fn try_find_address(&self, program_id: &Pubkey) -> CruiserResult<(Pubkey, u8)>;
     /// Creates an address from given seeds and nonce, ~50% chance to error if given a random nonce
     fn create_address(&self, program_id: &Pubkey, nonce: u8) -> CruiserResult<Pubkey>;
     /// Verifies that a given address is derived from given seeds and finds nonce. Returns the found nonce.
@@ -262,16 +492,27 @@ where
     }
 
     fn find_address(&self, program_id: &Pubkey) -> (Pubkey, u8) {
-        let seed_bytes = self.seeds_to_bytes().collect::<Vec<_>>();
-        Pubkey::find_program_address(&seed_bytes, program_id)
+        self.try_find_address(program_id)
+            .expect("Unable to find a viable program address")
+    }
+
+    fn try_find_address(&self, program_id: &Pubkey) -> CruiserResult<(Pubkey, u8)> {
+        let mut buffer = [&[] as &[u8]; MAX_SEEDS];
+        let count = self.write_seed_bytes(&mut buffer)?;
+        Pubkey::try_find_program_address(&buffer[..count], program_id).ok_or_else(|| {
+            GenericError::NoAccountFromSeeds {
+                seeds: self.seeds_to_strings().collect(),
+            }
+            .into()
+        })
     }
 
     fn create_address(&self, program_id: &Pubkey, nonce: u8) -> CruiserResult<Pubkey> {
-        Pubkey::create_program_address(
-            &self.seeds_to_bytes_with_nonce(&[nonce]).collect::<Vec<_>>(),
-            program_id,
-        )
-        .map_err(|error| match error {
+        let nonce_bytes = [nonce];
+        let mut buffer = [&[] as &[u8]; MAX_SEEDS];
+        let count = self.write_seed_bytes(&mut buffer)?;
+        buffer[count] = &nonce_bytes;
+        Pubkey::create_program_address(&buffer[..=count], program_id).map_err(|error| match error {
             PubkeyError::InvalidSeeds => GenericError::NoAccountFromSeeds {
                 seeds: self.seeds_to_strings_with_nonce(nonce).collect(),
             }
@@ -285,7 +526,7 @@ where
         program_id: &Pubkey,
         address: &Pubkey,
     ) -> CruiserResult<u8> {
-        let (key, nonce) = self.find_address(program_id);
+        let (key, nonce) = self.try_find_address(program_id)?;
         if address != &key {
             return Err(GenericError::AccountNotFromSeeds {
                 account: *address,
@@ -317,7 +558,7 @@ where
     }
 
     fn verify_address(&self, program_id: &Pubkey, address: &Pubkey) -> CruiserResult<()> {
-        let created_key = self.find_address(program_id).0;
+        let created_key = self.try_find_address(program_id)?.0;
         if address != &created_key {
             return Err(GenericError::AccountNotFromSeeds {
                 account: *address,
@@ -329,3 +570,114 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PDASeeder)]
+    #[seeder(prefix = "test")]
+    struct TestSeeder {
+        key: Pubkey,
+        index: [u8; 2],
+        name: Vec<u8>,
+    }
+
+    fn test_seeder() -> TestSeeder {
+        TestSeeder {
+            key: Pubkey::new_unique(),
+            index: [1, 2],
+            name: b"name".to_vec(),
+        }
+    }
+
+    #[test]
+    fn static_seeds_match_dynamic() {
+        let seeder = test_seeder();
+        let program_id = Pubkey::new_unique();
+
+        let dynamic = seeder.seeds().map(AsRef::as_ref).collect::<Vec<_>>();
+        let mut buffer = [&[] as &[u8]; MAX_SEEDS];
+        let count = seeder.write_seed_bytes(&mut buffer).unwrap();
+        assert_eq!(&buffer[..count], dynamic.as_slice());
+        assert_eq!(
+            seeder.find_address(&program_id),
+            Pubkey::find_program_address(&dynamic, &program_id)
+        );
+
+        let (key, seed_set) = PDASeedSet::find_borrowed(&seeder, &program_id);
+        seed_set.verify_address(&program_id, &key).unwrap();
+        assert_eq!(
+            TestSeeder::SEEDS,
+            &[
+                IdlSeed::Const { value: "test" },
+                IdlSeed::Field {
+                    name: "key",
+                    ty: "Pubkey"
+                },
+                IdlSeed::Field {
+                    name: "index",
+                    ty: "[u8; 2]"
+                },
+                IdlSeed::Field {
+                    name: "name",
+                    ty: "Vec<u8>"
+                },
+            ]
+        );
+    }
+
+    #[derive(Debug)]
+    struct TooManySeeds;
+    impl PDASeeder for TooManySeeds {
+        fn seeds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn PDASeed> + 'a> {
+            Box::new((0..MAX_SEEDS).map(|_| &"seed" as &dyn PDASeed))
+        }
+    }
+
+    #[test]
+    fn find_address_errors() {
+        assert!(TooManySeeds
+            .try_find_address(&Pubkey::new_unique())
+            .is_err());
+        assert!(PDASeedSet::try_find(TooManySeeds, &Pubkey::new_unique()).is_err());
+        assert!(PDASeedSet::try_find_borrowed(&TooManySeeds, &Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Unable to find a viable program address")]
+    fn find_address_panics() {
+        let _ = TooManySeeds.find_address(&Pubkey::new_unique());
+    }
+
+    #[test]
+    fn signer_seeds_past_stack_limit() {
+        let program_id = Pubkey::new_unique();
+        let seeders = (0..MAX_SIGNER_SEED_SETS * 2)
+            .map(|_| test_seeder())
+            .collect::<Vec<_>>();
+        let seed_sets = seeders
+            .iter()
+            .map(|seeder| PDASeedSet::find_borrowed(seeder, &program_id).1)
+            .collect::<Vec<_>>();
+        for count in 0..=seed_sets.len() {
+            let expected = seed_sets[..count]
+                .iter()
+                .map(|seed_set| {
+                    seed_set
+                        .seeds()
+                        .map(|seed| seed.as_ref().to_vec())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let signer_seeds = PDASeedSet::with_signer_seeds(&seed_sets[..count], |seeds| {
+                seeds
+                    .iter()
+                    .map(|seeds| seeds.iter().map(|seed| seed.to_vec()).collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+            assert_eq!(signer_seeds, expected);
+        }
+    }
+}
@@ -2,3 +2,5 @@
 
 #[cfg(feature = "spl-token")]
 pub mod token;
+#[cfg(feature = "spl-token")]
+pub mod token_2022;
@@ -0,0 +1,456 @@
+use std::ops::{Deref, DerefMut};
+
+use solana_program::entrypoint::ProgramResult;
+use solana_program::pubkey;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::rent;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::account_types::system_program::SystemProgram;
+use crate::cpi::CPI;
+use crate::pda_seeds::{PDAGenerator, PDASeed, PDASeedSet, PDASeeder};
+use crate::program::{Program, ProgramKey};
+use crate::spl::token::{TokenAccount, TokenChecks, TokenProgram, TokenProgramAccount};
+use crate::{
+    AccountInfo, CruiserResult, SolanaAccountMeta, SolanaInstruction, ToSolanaAccountInfo,
+};
+
+// verify_account_arg_impl! {
+//     mod associated_token_program_check<AI>{
+//         <AI> AssociatedTokenProgram<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [()];
+//             multi: [(); AllAny];
+//             single: [()];
+//         };
+//     }
+// }
+// verify_account_arg_impl! {
+//     mod associated_token_account_check<AI>{
+//         <AI> AssociatedTokenAccount<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [
+//                 <'a> Associated<'a>;
+//                 <'a, 'b, C> CreateAssociatedIfMissing<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI;
+//             ];
+//             multi: [<I> I where TokenAccount<AI>: MultiIndexable<AI, I>];
+//             single: [<I> I where TokenAccount<AI>: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// The seeder for associated token accounts, seeds are `[wallet, token_program, mint]` under the [`AssociatedTokenProgram`].
+#[derive(Debug, Copy, Clone)]
+pub struct AssociatedTokenSeeder<'a> {
+    /// The wallet that owns the token account
+    pub wallet: &'a Pubkey,
+    /// The token program the account belongs to
+    pub token_program: &'a Pubkey,
+    /// The mint of the token account
+    pub mint: &'a Pubkey,
+}
+impl<'a> AssociatedTokenSeeder<'a> {
+    /// Creates a seeder for the account of `wallet` for `mint` under `token_program`,
+    /// either the [`TokenProgram`] or the Token-2022 program
+    #[must_use]
+    pub fn new(wallet: &'a Pubkey, token_program: &'a Pubkey, mint: &'a Pubkey) -> Self {
+        Self {
+            wallet,
+            token_program,
+            mint,
+        }
+    }
+}
+impl<'b> PDASeeder for AssociatedTokenSeeder<'b> {
+    fn seeds<'a>(&'a self) -> Box<dyn Iterator<Item = &'a dyn PDASeed> + 'a> {
+        Box::new([self.wallet as &dyn PDASeed, self.token_program, self.mint].into_iter())
+    }
+}
+
+/// Gets the associated token account address of `wallet` for `mint` under `token_program`
+#[must_use]
+pub fn get_associated_token_address(
+    wallet: &Pubkey,
+    token_program: &Pubkey,
+    mint: &Pubkey,
+) -> Pubkey {
+    AssociatedTokenSeeder::new(wallet, token_program, mint)
+        .find_address(&AssociatedTokenProgram::<()>::KEY)
+        .0
+}
+
+/// Creates the associated token program's create instruction for an account under `token_program`
+#[must_use]
+pub fn create_associated_token_account_instruction(
+    funder: &Pubkey,
+    wallet: &Pubkey,
+    token_program: &Pubkey,
+    mint: &Pubkey,
+) -> SolanaInstruction {
+    SolanaInstruction {
+        program_id: AssociatedTokenProgram::<()>::KEY,
+        accounts: vec![
+            SolanaAccountMeta::new(*funder, true),
+            SolanaAccountMeta::new(
+                get_associated_token_address(wallet, token_program, mint),
+                false,
+            ),
+            SolanaAccountMeta::new_readonly(*wallet, false),
+            SolanaAccountMeta::new_readonly(*mint, false),
+            SolanaAccountMeta::new_readonly(SystemProgram::<()>::KEY, false),
+            SolanaAccountMeta::new_readonly(*token_program, false),
+            SolanaAccountMeta::new_readonly(rent::ID, false),
+        ],
+        data: vec![],
+    }
+}
+
+/// The SPL Associated Token Account Program.
+#[derive(AccountArgument, Debug, Clone)]
+#[account_argument(account_info = AI, generics = [where AI: AccountInfo])]
+pub struct AssociatedTokenProgram<AI> {
+    /// The program's info
+    #[validate(key = &Self::KEY)]
+    pub info: AI,
+}
+impl<AI> ProgramKey for AssociatedTokenProgram<AI> {
+    const KEY: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+}
+impl<AI> Program for AssociatedTokenProgram<AI> where AI: AccountInfo {}
+
+/// Argument for [`AssociatedTokenProgram::create`]
+#[derive(Copy, Clone, Debug)]
+pub struct CreateAssociated<'a, AI> {
+    /// The funder of the new account
+    pub funder: &'a AI,
+    /// The associated token account to create
+    pub account: &'a AI,
+    /// The wallet that will own the account
+    pub wallet: &'a AI,
+    /// The mint of the account
+    pub mint: &'a AI,
+    /// The system program
+    pub system_program: &'a SystemProgram<AI>,
+    /// The token program
+    pub token_program: &'a TokenProgram<AI>,
+    /// The rent sysvar
+    pub rent: &'a AI,
+}
+impl<'b, AI> AssociatedTokenProgram<AI>
+where
+    AI: ToSolanaAccountInfo<'b>,
+{
+    /// Calls the associated token program's create instruction
+    pub fn create<'a>(
+        &self,
+        cpi: impl CPI,
+        create: &CreateAssociated<AI>,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &create_associated_token_account_instruction(
+                create.funder.key(),
+                create.wallet.key(),
+                create.token_program.info.key(),
+                create.mint.key(),
+            ),
+            &[
+                &self.info,
+                create.funder,
+                create.account,
+                create.wallet,
+                create.mint,
+                &create.system_program.info,
+                &create.token_program.info,
+                create.rent,
+            ],
+            seeds,
+        )
+    }
+}
+impl<AI, T> MultiIndexable<T> for AssociatedTokenProgram<AI>
+where
+    AI: AccountInfo + MultiIndexable<T>,
+{
+    fn index_is_signer(&self, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, T> SingleIndexable<T> for AssociatedTokenProgram<AI>
+where
+    AI: AccountInfo + SingleIndexable<T>,
+{
+    fn index_info(&self, indexer: T) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
+
+/// A token account whose address is derived from a wallet and mint by the [`AssociatedTokenProgram`].
+/// May be uninitialized until validated with [`CreateAssociatedIfMissing`].
+#[derive(Debug)]
+pub struct AssociatedTokenAccount<AI> {
+    account: TokenAccount<AI>,
+}
+impl<AI> Deref for AssociatedTokenAccount<AI> {
+    type Target = TokenAccount<AI>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.account
+    }
+}
+impl<AI> DerefMut for AssociatedTokenAccount<AI> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.account
+    }
+}
+impl<AI> AccountArgument for AssociatedTokenAccount<AI>
+where
+    AI: AccountInfo,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.account.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.account.add_keys(add)
+    }
+}
+impl<AI> FromAccounts<()> for AssociatedTokenAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, arg)?;
+        let is_empty = info.data().is_empty();
+        let mut account = TokenAccount::uninitialized(TokenProgramAccount(info));
+        if !is_empty {
+            account.reload()?;
+        }
+        Ok(Self { account })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+/// Validates that the account is the initialized associated token account of `wallet` for `mint`.
+/// Also checks that the account is owned by `wallet` and holds `mint`.
+#[derive(Debug, Copy, Clone)]
+pub struct Associated<'a> {
+    /// The wallet that owns the account
+    pub wallet: &'a Pubkey,
+    /// The mint of the account
+    pub mint: &'a Pubkey,
+}
+impl<AI> ValidateArgument<Associated<'_>> for AssociatedTokenAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: Associated) -> CruiserResult<()> {
+        AssociatedTokenSeeder::new(arg.wallet, &TokenProgram::<()>::KEY, arg.mint).verify_address(
+            &AssociatedTokenProgram::<()>::KEY,
+            self.account.account.key(),
+        )?;
+        self.account.validate(
+            program_id,
+            TokenChecks {
+                mint: Some(arg.mint),
+                owner: Some(arg.wallet),
+                ..TokenChecks::default()
+            },
+        )
+    }
+}
+/// Validates the associated token account address and creates the account if it does not exist
+#[derive(Debug)]
+pub struct CreateAssociatedIfMissing<'a, AI, C> {
+    /// The wallet that owns the account
+    pub wallet: &'a AI,
+    /// The mint of the account
+    pub mint: &'a AI,
+    /// The funder for the account if it needs to be created, must be owned by the system program
+    pub funder: &'a AI,
+    /// The seeds for the funder if PDA
+    pub funder_seeds: Option<&'a PDASeedSet<'a>>,
+    /// The system program
+    pub system_program: &'a SystemProgram<AI>,
+    /// The token program
+    pub token_program: &'a TokenProgram<AI>,
+    /// The associated token program
+    pub associated_token_program: &'a AssociatedTokenProgram<AI>,
+    /// The rent sysvar
+    pub rent: &'a AI,
+    /// The CPI method to use
+    pub cpi: C,
+}
+impl<'a, 'b, AI, C> ValidateArgument<CreateAssociatedIfMissing<'a, AI, C>>
+    for AssociatedTokenAccount<AI>
+where
+    AI: ToSolanaAccountInfo<'b>,
+    C: CPI,
+{
+    fn validate(
+        &mut self,
+        program_id: &Pubkey,
+        arg: CreateAssociatedIfMissing<'a, AI, C>,
+    ) -> CruiserResult<()> {
+        let info = &self.account.account.0;
+        if info.data().is_empty() {
+            arg.associated_token_program.create(
+                arg.cpi,
+                &CreateAssociated {
+                    funder: arg.funder,
+                    account: info,
+                    wallet: arg.wallet,
+                    mint: arg.mint,
+                    system_program: arg.system_program,
+                    token_program: arg.token_program,
+                    rent: arg.rent,
+                },
+                arg.funder_seeds,
+            )?;
+            self.account.reload()?;
+        }
+        self.validate(
+            program_id,
+            Associated {
+                wallet: arg.wallet.key(),
+                mint: arg.mint.key(),
+            },
+        )
+    }
+}
+impl<AI, I> MultiIndexable<I> for AssociatedTokenAccount<AI>
+where
+    AI: AccountInfo,
+    TokenAccount<AI>: MultiIndexable<I>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, I> SingleIndexable<I> for AssociatedTokenAccount<AI>
+where
+    AI: AccountInfo,
+    TokenAccount<AI>: SingleIndexable<I, AccountInfo = AI>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.account.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use solana_program::program_option::COption;
+    use solana_program::program_pack::Pack;
+
+    use super::*;
+    use crate::spl::token_2022::Token2022Program;
+    use crate::SolanaAccountInfo;
+
+    #[test]
+    fn address_test() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        for token_program in [TokenProgram::<()>::KEY, Token2022Program::<()>::KEY] {
+            assert_eq!(
+                get_associated_token_address(&wallet, &token_program, &mint),
+                Pubkey::find_program_address(
+                    &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+                    &AssociatedTokenProgram::<()>::KEY,
+                )
+                .0
+            );
+        }
+        assert_ne!(
+            get_associated_token_address(&wallet, &TokenProgram::<()>::KEY, &mint),
+            get_associated_token_address(&wallet, &Token2022Program::<()>::KEY, &mint),
+        );
+
+        let funder = Pubkey::new_unique();
+        let instruction = create_associated_token_account_instruction(
+            &funder,
+            &wallet,
+            &Token2022Program::<()>::KEY,
+            &mint,
+        );
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            get_associated_token_address(&wallet, &Token2022Program::<()>::KEY, &mint)
+        );
+        assert_eq!(instruction.accounts[5].pubkey, Token2022Program::<()>::KEY);
+    }
+
+    #[test]
+    fn associated_validate_test() {
+        let program_id = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let key = get_associated_token_address(&wallet, &TokenProgram::<()>::KEY, &mint);
+        let owner = TokenProgram::<()>::KEY;
+
+        let validate = |data_owner: Pubkey, data_mint: Pubkey| {
+            let mut data = vec![0; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint: data_mint,
+                owner: data_owner,
+                state: spl_token::state::AccountState::Initialized,
+                delegate: COption::None,
+                is_native: COption::None,
+                close_authority: COption::None,
+                ..spl_token::state::Account::default()
+            }
+            .pack_into_slice(&mut data);
+            let mut lamports = 0;
+            let info = SolanaAccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &owner,
+                false,
+                0,
+            );
+            let mut account: AssociatedTokenAccount<_> =
+                FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ()).unwrap();
+            account.validate(
+                &program_id,
+                Associated {
+                    wallet: &wallet,
+                    mint: &mint,
+                },
+            )
+        };
+
+        assert!(validate(wallet, mint).is_ok());
+        assert!(validate(Pubkey::new_unique(), mint).is_err());
+        assert!(validate(wallet, Pubkey::new_unique()).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use std::ops::{Deref, DerefMut};
+
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::account_types::system_program::{Create, SystemProgram};
+use crate::cpi::CPI;
+use crate::on_chain_size::OnChainStaticSize;
+use crate::pda_seeds::PDASeedSet;
+use crate::program::ProgramKey;
+use crate::spl::token::{MintAccount, TokenProgram, TokenProgramAccount};
+use crate::{AccountInfo, CruiserResult, ToSolanaAccountInfo};
+
+// verify_account_arg_impl! {
+//     mod init_mint_account_check<AI>{
+//         <AI> InitMintAccount<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [<'a, 'b, C> InitMintAccountArgs<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy];
+//             multi: [<I> I where MintAccount<AI>: MultiIndexable<AI, I>];
+//             single: [<I> I where MintAccount<AI>: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// The arguments for initializing a mint
+#[derive(Debug)]
+pub struct InitMintAccountArgs<'a, AI, C> {
+    /// The system program to create the mint account
+    pub system_program: &'a SystemProgram<AI>,
+    /// The token program to initialize the mint account
+    pub token_program: &'a TokenProgram<AI>,
+    /// The mint authority of the new mint
+    pub mint_authority: &'a Pubkey,
+    /// The freeze authority of the new mint
+    pub freeze_authority: Option<&'a Pubkey>,
+    /// The decimals of the new mint
+    pub decimals: u8,
+    /// The funder for the newly created account, must be owned by the system program
+    pub funder: &'a AI,
+    /// The seeds for the funder if PDA
+    pub funder_seeds: Option<&'a PDASeedSet<'a>>,
+    /// The seeds for the mint account if PDA
+    pub account_seeds: Option<&'a PDASeedSet<'a>>,
+    /// The rent to use, if [`None`] will use [`Rent::get`].
+    pub rent: Option<Rent>,
+    /// The CPI method to use
+    pub cpi: C,
+}
+
+/// Creates and initializes a mint during validation. Acts like a [`MintAccount`] afterwards.
+/// Accounts that already hold lamports are topped up, allocated, and assigned instead of created.
+#[derive(Debug)]
+pub struct InitMintAccount<AI> {
+    account: MintAccount<AI>,
+}
+impl<AI> Deref for InitMintAccount<AI> {
+    type Target = MintAccount<AI>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.account
+    }
+}
+impl<AI> DerefMut for InitMintAccount<AI> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.account
+    }
+}
+impl<AI> AccountArgument for InitMintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.account.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.account.add_keys(add)
+    }
+}
+impl<AI> FromAccounts<()> for InitMintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, arg)?;
+        Ok(Self {
+            account: MintAccount::uninitialized(TokenProgramAccount(info)),
+        })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<'a, 'b, AI, C> ValidateArgument<InitMintAccountArgs<'a, AI, C>> for InitMintAccount<AI>
+where
+    AI: ToSolanaAccountInfo<'b>,
+    C: CPI + Copy,
+{
+    fn validate(
+        &mut self,
+        program_id: &Pubkey,
+        arg: InitMintAccountArgs<'a, AI, C>,
+    ) -> CruiserResult<()> {
+        let space = MintAccount::<AI>::on_chain_static_size();
+        let rent = match arg.rent {
+            None => Rent::get()?,
+            Some(rent) => rent,
+        }
+        .minimum_balance(space);
+
+        let info = &self.account.account.0;
+        arg.system_program.create_or_allocate_account(
+            arg.cpi,
+            &Create {
+                funder: arg.funder,
+                account: info,
+                lamports: rent,
+                space: space as u64,
+                owner: &TokenProgram::<AI>::KEY,
+            },
+            arg.funder_seeds,
+            arg.account_seeds,
+        )?;
+        arg.token_program.initialize_mint2(
+            arg.cpi,
+            info,
+            arg.mint_authority,
+            arg.freeze_authority,
+            arg.decimals,
+            [],
+        )?;
+
+        self.account.reload()?;
+        self.account.validate(program_id, ())
+    }
+}
+impl<AI, I> MultiIndexable<I> for InitMintAccount<AI>
+where
+    AI: AccountInfo,
+    MintAccount<AI>: MultiIndexable<I>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, I> SingleIndexable<I> for InitMintAccount<AI>
+where
+    AI: AccountInfo,
+    MintAccount<AI>: SingleIndexable<I, AccountInfo = AI>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.account.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use solana_program::program_option::COption;
+    use solana_program::program_pack::Pack;
+    use solana_program::system_instruction::SystemInstruction;
+    use spl_token::instruction::TokenInstruction;
+    use spl_token::state::Mint;
+
+    use super::*;
+    use crate::cpi::CPIChecked;
+    use crate::util::invoke_recorder::record_invokes;
+    use crate::{CruiserAccountInfo, SolanaInstruction};
+
+    fn account_info(
+        key: Pubkey,
+        owner: Pubkey,
+        data: Vec<u8>,
+        lamports: u64,
+        is_signer: bool,
+    ) -> CruiserAccountInfo {
+        let data = Box::leak(data.into_boxed_slice());
+        CruiserAccountInfo {
+            key: Box::leak(Box::new(key)),
+            is_signer,
+            is_writable: true,
+            lamports: Rc::new(RefCell::new(Box::leak(Box::new(lamports)))),
+            original_data_len: Box::leak(Box::new(data.len())),
+            data: Rc::new(RefCell::new(data)),
+            owner: Box::leak(Box::new(RefCell::new(Box::leak(Box::new(owner))))),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    /// Initializes a mint already holding `lamports`, returning the invoked system instructions.
+    /// The mint data is written up front as the invoked instructions are not run.
+    fn init_system_invokes(lamports: u64) -> Vec<SystemInstruction> {
+        let program_id = Pubkey::new_unique();
+        let token_program_key = TokenProgram::<()>::KEY;
+        let mint_authority = Pubkey::new_unique();
+
+        let mut data = vec![0; Mint::LEN];
+        Mint {
+            mint_authority: COption::Some(mint_authority),
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut data);
+        let info = account_info(
+            Pubkey::new_unique(),
+            token_program_key,
+            data,
+            lamports,
+            true,
+        );
+        let mut account: InitMintAccount<_> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ()).unwrap();
+
+        let system_program = SystemProgram {
+            info: account_info(
+                SystemProgram::<()>::KEY,
+                Pubkey::new_unique(),
+                vec![],
+                0,
+                false,
+            ),
+        };
+        let token_program = TokenProgram {
+            info: account_info(token_program_key, Pubkey::new_unique(), vec![], 0, false),
+        };
+        let funder = account_info(
+            Pubkey::new_unique(),
+            SystemProgram::<()>::KEY,
+            vec![],
+            1_000_000_000,
+            true,
+        );
+
+        let (result, invoked) = record_invokes(|| {
+            account.validate(
+                &program_id,
+                InitMintAccountArgs {
+                    system_program: &system_program,
+                    token_program: &token_program,
+                    mint_authority: &mint_authority,
+                    freeze_authority: None,
+                    decimals: 6,
+                    funder: &funder,
+                    funder_seeds: None,
+                    account_seeds: None,
+                    rent: Some(Rent::default()),
+                    cpi: CPIChecked,
+                },
+            )
+        });
+        result.unwrap();
+
+        let (last, system) = invoked.split_last().unwrap();
+        assert_eq!(last.program_id, token_program_key);
+        assert_eq!(
+            TokenInstruction::unpack(&last.data).unwrap(),
+            TokenInstruction::InitializeMint2 {
+                decimals: 6,
+                mint_authority,
+                freeze_authority: COption::None,
+            }
+        );
+        system
+            .iter()
+            .map(|instruction: &SolanaInstruction| {
+                assert_eq!(instruction.program_id, SystemProgram::<()>::KEY);
+                bincode::deserialize(&instruction.data).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn init_mint_account_test() {
+        let rent = Rent::default().minimum_balance(Mint::LEN);
+        let space = Mint::LEN as u64;
+        let owner = TokenProgram::<()>::KEY;
+
+        assert_eq!(
+            init_system_invokes(0),
+            [SystemInstruction::CreateAccount {
+                lamports: rent,
+                space,
+                owner
+            }]
+        );
+        assert_eq!(
+            init_system_invokes(1),
+            [
+                SystemInstruction::Transfer { lamports: rent - 1 },
+                SystemInstruction::Allocate { space },
+                SystemInstruction::Assign { owner },
+            ]
+        );
+        assert_eq!(
+            init_system_invokes(rent),
+            [
+                SystemInstruction::Allocate { space },
+                SystemInstruction::Assign { owner },
+            ]
+        );
+    }
+}
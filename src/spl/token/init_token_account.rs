@@ -0,0 +1,338 @@
+use std::ops::{Deref, DerefMut};
+
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::account_types::system_program::{Create, SystemProgram};
+use crate::cpi::CPI;
+use crate::on_chain_size::OnChainStaticSize;
+use crate::pda_seeds::PDASeedSet;
+use crate::program::ProgramKey;
+use crate::spl::token::{MintAccount, TokenAccount, TokenProgram, TokenProgramAccount};
+use crate::{AccountInfo, CruiserResult, ToSolanaAccountInfo};
+
+// verify_account_arg_impl! {
+//     mod init_token_account_check<AI>{
+//         <AI> InitTokenAccount<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [<'a, 'b, C> InitTokenAccountArgs<'a, AI, C> where AI: 'a + ToSolanaAccountInfo<'b>, C: CPI + Copy];
+//             multi: [<I> I where TokenAccount<AI>: MultiIndexable<AI, I>];
+//             single: [<I> I where TokenAccount<AI>: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// The arguments for initializing a token account
+#[derive(Debug)]
+pub struct InitTokenAccountArgs<'a, AI, C> {
+    /// The system program to create the account
+    pub system_program: &'a SystemProgram<AI>,
+    /// The token program to initialize the account
+    pub token_program: &'a TokenProgram<AI>,
+    /// The mint of the new token account
+    pub mint: &'a MintAccount<AI>,
+    /// The owner of the new token account
+    pub owner: &'a Pubkey,
+    /// The funder for the newly created account, must be owned by the system program
+    pub funder: &'a AI,
+    /// The seeds for the funder if PDA
+    pub funder_seeds: Option<&'a PDASeedSet<'a>>,
+    /// The seeds for the account if PDA
+    pub account_seeds: Option<&'a PDASeedSet<'a>>,
+    /// The rent to use, if [`None`] will use [`Rent::get`].
+    pub rent: Option<Rent>,
+    /// The CPI method to use
+    pub cpi: C,
+}
+
+/// Creates and initializes a token account during validation. Acts like a [`TokenAccount`] afterwards.
+/// Accounts that already hold lamports are topped up, allocated, and assigned instead of created.
+#[derive(Debug)]
+pub struct InitTokenAccount<AI> {
+    account: TokenAccount<AI>,
+}
+impl<AI> Deref for InitTokenAccount<AI> {
+    type Target = TokenAccount<AI>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.account
+    }
+}
+impl<AI> DerefMut for InitTokenAccount<AI> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.account
+    }
+}
+impl<AI> AccountArgument for InitTokenAccount<AI>
+where
+    AI: AccountInfo,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.account.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.account.add_keys(add)
+    }
+}
+impl<AI> FromAccounts<()> for InitTokenAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let info = AI::from_accounts(program_id, infos, arg)?;
+        Ok(Self {
+            account: TokenAccount::uninitialized(TokenProgramAccount(info)),
+        })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<'a, 'b, AI, C> ValidateArgument<InitTokenAccountArgs<'a, AI, C>> for InitTokenAccount<AI>
+where
+    AI: ToSolanaAccountInfo<'b>,
+    C: CPI + Copy,
+{
+    fn validate(
+        &mut self,
+        program_id: &Pubkey,
+        arg: InitTokenAccountArgs<'a, AI, C>,
+    ) -> CruiserResult<()> {
+        let space = TokenAccount::<AI>::on_chain_static_size();
+        let rent = match arg.rent {
+            None => Rent::get()?,
+            Some(rent) => rent,
+        }
+        .minimum_balance(space);
+
+        let info = &self.account.account.0;
+        arg.system_program.create_or_allocate_account(
+            arg.cpi,
+            &Create {
+                funder: arg.funder,
+                account: info,
+                lamports: rent,
+                space: space as u64,
+                owner: &TokenProgram::<AI>::KEY,
+            },
+            arg.funder_seeds,
+            arg.account_seeds,
+        )?;
+        arg.token_program
+            .initialize_account3(arg.cpi, info, arg.mint, arg.owner, [])?;
+
+        self.account.reload()?;
+        self.account.validate(program_id, ())
+    }
+}
+impl<AI, I> MultiIndexable<I> for InitTokenAccount<AI>
+where
+    AI: AccountInfo,
+    TokenAccount<AI>: MultiIndexable<I>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, I> SingleIndexable<I> for InitTokenAccount<AI>
+where
+    AI: AccountInfo,
+    TokenAccount<AI>: SingleIndexable<I, AccountInfo = AI>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.account.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use solana_program::program_option::COption;
+    use solana_program::program_pack::Pack;
+    use solana_program::system_instruction::SystemInstruction;
+    use spl_token::instruction::TokenInstruction;
+    use spl_token::state::{Account, AccountState, Mint};
+
+    use super::*;
+    use crate::cpi::CPIChecked;
+    use crate::util::invoke_recorder::record_invokes;
+    use crate::{CruiserAccountInfo, SolanaInstruction};
+
+    fn account_info(
+        key: Pubkey,
+        owner: Pubkey,
+        data: Vec<u8>,
+        lamports: u64,
+        is_signer: bool,
+    ) -> CruiserAccountInfo {
+        let data = Box::leak(data.into_boxed_slice());
+        CruiserAccountInfo {
+            key: Box::leak(Box::new(key)),
+            is_signer,
+            is_writable: true,
+            lamports: Rc::new(RefCell::new(Box::leak(Box::new(lamports)))),
+            original_data_len: Box::leak(Box::new(data.len())),
+            data: Rc::new(RefCell::new(data)),
+            owner: Box::leak(Box::new(RefCell::new(Box::leak(Box::new(owner))))),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    /// Initializes a token account already holding `lamports`, returning the invoked instructions.
+    /// The account data is written up front as the invoked instructions are not run.
+    fn init_invokes(lamports: u64) -> Vec<SolanaInstruction> {
+        let program_id = Pubkey::new_unique();
+        let token_program_key = TokenProgram::<()>::KEY;
+        let owner = Pubkey::new_unique();
+
+        let mut mint_data = vec![0; Mint::LEN];
+        Mint {
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut mint_data);
+        let mint: MintAccount<_> = FromAccounts::from_accounts(
+            &program_id,
+            &mut [account_info(
+                Pubkey::new_unique(),
+                token_program_key,
+                mint_data,
+                0,
+                false,
+            )]
+            .into_iter(),
+            (),
+        )
+        .unwrap();
+
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint: *mint.account.0.key,
+            owner,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        let info = account_info(
+            Pubkey::new_unique(),
+            token_program_key,
+            data,
+            lamports,
+            true,
+        );
+        let mut account: InitTokenAccount<_> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ()).unwrap();
+
+        let system_program = SystemProgram {
+            info: account_info(
+                SystemProgram::<()>::KEY,
+                Pubkey::new_unique(),
+                vec![],
+                0,
+                false,
+            ),
+        };
+        let token_program = TokenProgram {
+            info: account_info(token_program_key, Pubkey::new_unique(), vec![], 0, false),
+        };
+        let funder = account_info(
+            Pubkey::new_unique(),
+            SystemProgram::<()>::KEY,
+            vec![],
+            1_000_000_000,
+            true,
+        );
+
+        let (result, invoked) = record_invokes(|| {
+            account.validate(
+                &program_id,
+                InitTokenAccountArgs {
+                    system_program: &system_program,
+                    token_program: &token_program,
+                    mint: &mint,
+                    owner: &owner,
+                    funder: &funder,
+                    funder_seeds: None,
+                    account_seeds: None,
+                    rent: Some(Rent::default()),
+                    cpi: CPIChecked,
+                },
+            )
+        });
+        result.unwrap();
+
+        let last = invoked.last().unwrap();
+        assert_eq!(last.program_id, token_program_key);
+        assert_eq!(
+            TokenInstruction::unpack(&last.data).unwrap(),
+            TokenInstruction::InitializeAccount3 { owner }
+        );
+        invoked
+    }
+
+    fn system_instructions(invoked: &[SolanaInstruction]) -> Vec<SystemInstruction> {
+        invoked[..invoked.len() - 1]
+            .iter()
+            .map(|instruction| {
+                assert_eq!(instruction.program_id, SystemProgram::<()>::KEY);
+                bincode::deserialize(&instruction.data).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn init_token_account_test() {
+        let rent = Rent::default().minimum_balance(Account::LEN);
+        let space = Account::LEN as u64;
+        let owner = TokenProgram::<()>::KEY;
+
+        assert_eq!(
+            system_instructions(&init_invokes(0)),
+            [SystemInstruction::CreateAccount {
+                lamports: rent,
+                space,
+                owner
+            }]
+        );
+        assert_eq!(
+            system_instructions(&init_invokes(1)),
+            [
+                SystemInstruction::Transfer { lamports: rent - 1 },
+                SystemInstruction::Allocate { space },
+                SystemInstruction::Assign { owner },
+            ]
+        );
+        assert_eq!(
+            system_instructions(&init_invokes(rent)),
+            [
+                SystemInstruction::Allocate { space },
+                SystemInstruction::Assign { owner },
+            ]
+        );
+    }
+}
@@ -10,13 +10,13 @@ use cruiser::AccountInfo;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
 
-use crate::spl::token::TokenProgramAccount;
+use crate::spl::token::{token_check, TokenProgramAccount};
 
 // verify_account_arg_impl! {
 //     mod mint_account_check<AI>{
 //         <AI> MintAccount<AI> where AI: AccountInfo{
 //             from: [()];
-//             validate: [()];
+//             validate: [(); <'a> MintChecks<'a>];
 //             multi: [<I> I where TokenProgramAccount<AI>: MultiIndexable<AI, I>];
 //             single: [<I> I where TokenProgramAccount<AI>: SingleIndexable<AI, I>];
 //         }
@@ -30,6 +30,24 @@ pub struct MintAccount<AI> {
     /// The account associated
     pub account: TokenProgramAccount<AI>,
 }
+impl<AI> MintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    /// Creates this without reading the data, used for accounts that are not yet initialized.
+    pub(crate) fn uninitialized(account: TokenProgramAccount<AI>) -> Self {
+        Self {
+            data: spl_token::state::Mint::default(),
+            account,
+        }
+    }
+
+    /// Re-reads the data from the account, needed after CPIs that change it.
+    pub fn reload(&mut self) -> CruiserResult<()> {
+        self.data = spl_token::state::Mint::unpack(&*self.account.data())?;
+        Ok(())
+    }
+}
 impl<AI> OnChainSize<()> for MintAccount<AI> {
     fn on_chain_max_size(_arg: ()) -> usize {
         spl_token::state::Mint::get_packed_len()
@@ -85,6 +103,79 @@ where
         Ok(())
     }
 }
+/// Composable checks for a [`MintAccount`], [`None`] fields are not checked.
+///
+/// Use with struct update syntax: `MintChecks { decimals: Some(6), ..MintChecks::default() }`
+#[derive(Debug, Default, Copy, Clone)]
+pub struct MintChecks<'a> {
+    /// The mint authority, `Some(None)` requires a fixed supply mint
+    pub mint_authority: Option<Option<&'a Pubkey>>,
+    /// The freeze authority, `Some(None)` requires no freeze authority
+    pub freeze_authority: Option<Option<&'a Pubkey>>,
+    /// The decimals of the mint
+    pub decimals: Option<u8>,
+    /// The minimum supply of the mint (inclusive)
+    pub min_supply: Option<u64>,
+    /// The maximum supply of the mint (inclusive)
+    pub max_supply: Option<u64>,
+}
+impl<AI> ValidateArgument<MintChecks<'_>> for MintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: MintChecks) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        let key = self.account.key();
+        if let Some(mint_authority) = arg.mint_authority {
+            let account_mint_authority = Option::<Pubkey>::from(self.data.mint_authority);
+            token_check(
+                key,
+                "mint_authority",
+                account_mint_authority.as_ref() == mint_authority,
+                mint_authority,
+                account_mint_authority,
+            )?;
+        }
+        if let Some(freeze_authority) = arg.freeze_authority {
+            let account_freeze_authority = Option::<Pubkey>::from(self.data.freeze_authority);
+            token_check(
+                key,
+                "freeze_authority",
+                account_freeze_authority.as_ref() == freeze_authority,
+                freeze_authority,
+                account_freeze_authority,
+            )?;
+        }
+        if let Some(decimals) = arg.decimals {
+            token_check(
+                key,
+                "decimals",
+                self.data.decimals == decimals,
+                decimals,
+                self.data.decimals,
+            )?;
+        }
+        if let Some(min_supply) = arg.min_supply {
+            token_check(
+                key,
+                "min_supply",
+                self.data.supply >= min_supply,
+                min_supply,
+                self.data.supply,
+            )?;
+        }
+        if let Some(max_supply) = arg.max_supply {
+            token_check(
+                key,
+                "max_supply",
+                self.data.supply <= max_supply,
+                max_supply,
+                self.data.supply,
+            )?;
+        }
+        Ok(())
+    }
+}
 impl<AI, I> MultiIndexable<I> for MintAccount<AI>
 where
     AI: AccountInfo,
@@ -111,3 +202,130 @@ where
         self.account.index_info(indexer)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use solana_program::program_option::COption;
+    use spl_token::state::Mint;
+
+    use super::*;
+    use crate::program::ProgramKey;
+    use crate::spl::token::TokenProgram;
+    use crate::SolanaAccountInfo;
+
+    fn validate(data: Mint, checks: MintChecks) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let owner = TokenProgram::<()>::KEY;
+        let mut account_data = vec![0; Mint::LEN];
+        data.pack_into_slice(&mut account_data);
+        let mut lamports = 0;
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut account_data,
+            &owner,
+            false,
+            0,
+        );
+        let mut account: MintAccount<_> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+        account.validate(&program_id, checks)
+    }
+
+    fn assert_failed(result: CruiserResult<()>, check: &str) {
+        let message = result.unwrap_err().message();
+        assert!(
+            message.starts_with(&format!("Token check `{}` failed", check)),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn mint_checks_test() {
+        let mint_authority = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let data = Mint {
+            mint_authority: COption::Some(mint_authority),
+            supply: 100,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let all = MintChecks {
+            mint_authority: Some(Some(&mint_authority)),
+            freeze_authority: Some(None),
+            decimals: Some(6),
+            min_supply: Some(100),
+            max_supply: Some(100),
+        };
+
+        validate(data, MintChecks::default()).unwrap();
+        validate(data, all).unwrap();
+        validate(
+            data,
+            MintChecks {
+                decimals: Some(6),
+                ..MintChecks::default()
+            },
+        )
+        .unwrap();
+
+        for (checks, failed) in [
+            (
+                MintChecks {
+                    mint_authority: Some(None),
+                    ..all
+                },
+                "mint_authority",
+            ),
+            (
+                MintChecks {
+                    freeze_authority: Some(Some(&other)),
+                    ..all
+                },
+                "freeze_authority",
+            ),
+            (
+                MintChecks {
+                    decimals: Some(9),
+                    ..all
+                },
+                "decimals",
+            ),
+            (
+                MintChecks {
+                    min_supply: Some(101),
+                    ..all
+                },
+                "min_supply",
+            ),
+            (
+                MintChecks {
+                    max_supply: Some(99),
+                    ..all
+                },
+                "max_supply",
+            ),
+        ] {
+            assert_failed(validate(data, checks), failed);
+        }
+
+        let fixed_supply = Mint {
+            mint_authority: COption::None,
+            ..data
+        };
+        validate(
+            fixed_supply,
+            MintChecks {
+                mint_authority: Some(None),
+                ..all
+            },
+        )
+        .unwrap();
+        assert_failed(validate(fixed_supply, all), "mint_authority");
+    }
+}
@@ -1,16 +1,25 @@
 //! Implementations for spl tokens
 
+mod associated_token_account;
+mod init_mint_account;
+mod init_token_account;
 mod mint_account;
+mod multisig;
 mod program;
 mod token_account;
 
+pub use associated_token_account::*;
+pub use init_mint_account::*;
+pub use init_token_account::*;
 pub use mint_account::*;
+pub use multisig::*;
 pub use program::*;
 pub use token_account::*;
 
 use crate::account_argument::{AccountArgument, MultiIndexable, SingleIndexable};
-use crate::{AccountInfo, CruiserResult};
+use crate::{AccountInfo, CruiserResult, GenericError};
 use solana_program::pubkey::Pubkey;
+use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
 
 // verify_account_arg_impl! {
@@ -64,3 +73,24 @@ where
         self.0.index_info(indexer)
     }
 }
+
+/// Errors with [`GenericError::TokenCheckFailed`] if `valid` is false
+fn token_check(
+    account: &Pubkey,
+    check: &str,
+    valid: bool,
+    expected: impl Debug,
+    received: impl Debug,
+) -> CruiserResult<()> {
+    if valid {
+        Ok(())
+    } else {
+        Err(GenericError::TokenCheckFailed {
+            account: *account,
+            check: check.to_string(),
+            expected: format!("{:?}", expected),
+            received: format!("{:?}", received),
+        }
+        .into())
+    }
+}
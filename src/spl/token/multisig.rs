@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+use std::ops::Deref;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::on_chain_size::OnChainSize;
+use crate::{AccountInfo, CruiserResult, GenericError};
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+
+use crate::spl::token::TokenProgramAccount;
+
+// verify_account_arg_impl! {
+//     mod token_multisig_check<AI>{
+//         <AI> TokenMultisig<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [(); <'a> MultisigSigners<'a, AI>];
+//             multi: [<I> I where TokenProgramAccount<AI>: MultiIndexable<AI, I>];
+//             single: [<I> I where TokenProgramAccount<AI>: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// A multisig authority owned by the token program
+#[derive(Debug)]
+pub struct TokenMultisig<AI> {
+    data: spl_token::state::Multisig,
+    /// The account associated
+    pub account: TokenProgramAccount<AI>,
+}
+impl<AI> TokenMultisig<AI> {
+    /// The valid signers of this multisig, all signer slots if `n` is larger than [`MAX_SIGNERS`](spl_token::instruction::MAX_SIGNERS)
+    #[must_use]
+    pub fn valid_signers(&self) -> &[Pubkey] {
+        self.data
+            .signers
+            .get(..self.data.n as usize)
+            .unwrap_or(&self.data.signers)
+    }
+
+    /// Returns true if `key` is a valid signer of this multisig
+    #[must_use]
+    pub fn is_valid_signer(&self, key: &Pubkey) -> bool {
+        self.valid_signers().contains(key)
+    }
+}
+impl<AI> OnChainSize<()> for TokenMultisig<AI> {
+    fn on_chain_max_size(_arg: ()) -> usize {
+        spl_token::state::Multisig::get_packed_len()
+    }
+}
+impl<AI> Deref for TokenMultisig<AI> {
+    type Target = spl_token::state::Multisig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+impl<AI> AccountArgument for TokenMultisig<AI>
+where
+    AI: AccountInfo,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.account.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.account.add_keys(add)
+    }
+}
+impl<AI> FromAccounts<()> for TokenMultisig<AI>
+where
+    AI: AccountInfo,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let account: TokenProgramAccount<AI> = FromAccounts::from_accounts(program_id, infos, arg)?;
+        let data = spl_token::state::Multisig::unpack(&*account.data())?;
+        Ok(Self { data, account })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        TokenProgramAccount::<AI>::accounts_usage_hint(arg)
+    }
+}
+impl<AI> ValidateArgument<()> for TokenMultisig<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.account.validate(program_id, arg)
+    }
+}
+/// Validates that enough of the given accounts are signing valid signers of the [`TokenMultisig`]
+#[derive(Debug)]
+pub struct MultisigSigners<'a, AI>(pub &'a [AI]);
+impl<AI> ValidateArgument<MultisigSigners<'_, AI>> for TokenMultisig<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: MultisigSigners<AI>) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        let signed = arg
+            .0
+            .iter()
+            .filter(|signer| signer.is_signer() && self.is_valid_signer(signer.key()))
+            .map(|signer| *signer.key())
+            .collect::<HashSet<_>>();
+        if signed.len() >= self.data.m as usize {
+            Ok(())
+        } else {
+            Err(GenericError::NotEnoughMultisigSigners {
+                multisig: *self.account.key(),
+                needed: self.data.m,
+                signed: signed.len(),
+            }
+            .into())
+        }
+    }
+}
+impl<AI, I> MultiIndexable<I> for TokenMultisig<AI>
+where
+    AI: AccountInfo,
+    TokenProgramAccount<AI>: MultiIndexable<I>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, I> SingleIndexable<I> for TokenMultisig<AI>
+where
+    AI: AccountInfo,
+    TokenProgramAccount<AI>: SingleIndexable<I, AccountInfo = AI>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.account.index_info(indexer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use spl_token::instruction::MAX_SIGNERS;
+    use spl_token::state::Multisig;
+
+    use super::*;
+    use crate::program::ProgramKey;
+    use crate::spl::token::TokenProgram;
+    use crate::SolanaAccountInfo;
+
+    /// Validates a multisig of `m` with `n` signers from `signer_keys` against `signed`, which are `(key, is_signer)`
+    fn validate(
+        m: u8,
+        n: u8,
+        signer_keys: &[Pubkey],
+        signed: &[(Pubkey, bool)],
+    ) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let owner = TokenProgram::<()>::KEY;
+        let mut signers = [Pubkey::default(); MAX_SIGNERS];
+        signers[..signer_keys.len()].copy_from_slice(signer_keys);
+        let mut data = vec![0; Multisig::LEN];
+        Multisig {
+            m,
+            n,
+            is_initialized: true,
+            signers,
+        }
+        .pack_into_slice(&mut data);
+        let mut lamports = 0;
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let mut account: TokenMultisig<_> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+
+        let system_program = Pubkey::default();
+        let mut lamports = vec![0; signed.len()];
+        let mut data = vec![vec![]; signed.len()];
+        let signer_infos = signed
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|(((key, is_signer), lamports), data)| {
+                SolanaAccountInfo::new(
+                    key,
+                    *is_signer,
+                    false,
+                    lamports,
+                    data,
+                    &system_program,
+                    false,
+                    0,
+                )
+            })
+            .collect::<Vec<_>>();
+        account.validate(&program_id, MultisigSigners(&signer_infos))
+    }
+
+    #[test]
+    fn multisig_signers_test() {
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let other = Pubkey::new_unique();
+
+        validate(2, 3, &keys, &[(keys[0], true), (keys[2], true)]).unwrap();
+        validate(0, 3, &keys, &[]).unwrap();
+        // Not signing
+        assert!(validate(2, 3, &keys, &[(keys[0], true), (keys[1], false)]).is_err());
+        // Duplicates only count once
+        assert!(validate(2, 3, &keys, &[(keys[0], true), (keys[0], true)]).is_err());
+        // Not a signer of the multisig
+        assert!(validate(2, 3, &keys, &[(keys[0], true), (other, true)]).is_err());
+        // Outside of the first `n` signers
+        assert!(validate(2, 2, &keys, &[(keys[0], true), (keys[2], true)]).is_err());
+
+        let message = validate(3, 3, &keys, &[(keys[0], true)])
+            .unwrap_err()
+            .message();
+        assert!(
+            message.ends_with("needs `3` signers, only `1` valid signers signed"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn oversized_n_test() {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        validate(2, u8::MAX, &keys, &[(keys[0], true), (keys[1], true)]).unwrap();
+    }
+}
@@ -2,13 +2,17 @@ use crate::account_argument::{AccountArgument, MultiIndexable, Single, SingleInd
 use crate::cpi::CPI;
 use crate::pda_seeds::PDASeedSet;
 use crate::program::{Program, ProgramKey};
-use crate::{AccountInfo, CruiserResult, ToSolanaAccountInfo};
+use crate::{AccountInfo, AccountInfoAccess, CruiserResult, ToSolanaAccountInfo};
 use solana_program::entrypoint::ProgramResult;
 use solana_program::pubkey;
 use solana_program::pubkey::Pubkey;
-use spl_token::instruction::{close_account, set_authority, transfer, AuthorityType};
+use spl_token::instruction::{
+    approve, burn, burn_checked, close_account, freeze_account, initialize_account,
+    initialize_account3, initialize_mint, initialize_mint2, mint_to, mint_to_checked, revoke,
+    set_authority, sync_native, thaw_account, transfer, transfer_checked, AuthorityType,
+};
 
-use crate::spl::token::TokenAccount;
+use crate::spl::token::{MintAccount, TokenAccount, TokenMultisig};
 
 // verify_account_arg_impl! {
 //     mod token_program_check<AI>{
@@ -112,6 +116,450 @@ where
             seeds,
         )
     }
+
+    /// Calls the token program's [`set_authority`] instruction with a multisig owner
+    pub fn set_authority_multisig<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        new_authority: &Pubkey,
+        owner: &TokenMultisig<AI>,
+        signers: &[AI],
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        let owner_info = owner.info();
+        let signer_keys = signers
+            .iter()
+            .map(AccountInfoAccess::key)
+            .collect::<Vec<_>>();
+        PDASeedSet::invoke_signed_variable_size_multiple(
+            cpi,
+            &set_authority(
+                &spl_token::ID,
+                account_info.key(),
+                Some(new_authority),
+                AuthorityType::AccountOwner,
+                owner_info.key(),
+                &signer_keys,
+            )?,
+            [&self.info, account_info, owner_info]
+                .into_iter()
+                .chain(signers),
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`transfer`] instruction with a multisig authority
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_multisig<'a>(
+        &self,
+        cpi: impl CPI,
+        from: &TokenAccount<AI>,
+        to: &TokenAccount<AI>,
+        authority: &TokenMultisig<AI>,
+        signers: &[AI],
+        amount: u64,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let from_info = from.info();
+        let to_info = to.info();
+        let authority_info = authority.info();
+        let signer_keys = signers
+            .iter()
+            .map(AccountInfoAccess::key)
+            .collect::<Vec<_>>();
+        PDASeedSet::invoke_signed_variable_size_multiple(
+            cpi,
+            &transfer(
+                &spl_token::ID,
+                from_info.key(),
+                to_info.key(),
+                authority_info.key(),
+                &signer_keys,
+                amount,
+            )?,
+            [&self.info, from_info, to_info, authority_info]
+                .into_iter()
+                .chain(signers),
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`close_account`] instruction with a multisig authority
+    pub fn close_account_multisig<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        destination: &AI,
+        authority: &TokenMultisig<AI>,
+        signers: &[AI],
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        let authority_info = authority.info();
+        let signer_keys = signers
+            .iter()
+            .map(AccountInfoAccess::key)
+            .collect::<Vec<_>>();
+        PDASeedSet::invoke_signed_variable_size_multiple(
+            cpi,
+            &close_account(
+                &spl_token::ID,
+                account_info.key(),
+                destination.key(),
+                authority_info.key(),
+                &signer_keys,
+            )?,
+            [&self.info, account_info, destination, authority_info]
+                .into_iter()
+                .chain(signers),
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`initialize_mint`] instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_mint<'a>(
+        &self,
+        cpi: impl CPI,
+        mint: &AI,
+        rent: &AI,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &initialize_mint(
+                &spl_token::ID,
+                mint.key(),
+                mint_authority,
+                freeze_authority,
+                decimals,
+            )?,
+            &[&self.info, mint, rent],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`initialize_mint2`] instruction.
+    /// Does not require the rent account.
+    pub fn initialize_mint2<'a>(
+        &self,
+        cpi: impl CPI,
+        mint: &AI,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+        decimals: u8,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &initialize_mint2(
+                &spl_token::ID,
+                mint.key(),
+                mint_authority,
+                freeze_authority,
+                decimals,
+            )?,
+            &[&self.info, mint],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`initialize_account`] instruction
+    pub fn initialize_account<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &AI,
+        mint: &MintAccount<AI>,
+        owner: &AI,
+        rent: &AI,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let mint_info = mint.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &initialize_account(&spl_token::ID, account.key(), mint_info.key(), owner.key())?,
+            &[&self.info, account, mint_info, owner, rent],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`initialize_account3`] instruction.
+    /// Does not require the owner or rent accounts.
+    pub fn initialize_account3<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &AI,
+        mint: &MintAccount<AI>,
+        owner: &Pubkey,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let mint_info = mint.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &initialize_account3(&spl_token::ID, account.key(), mint_info.key(), owner)?,
+            &[&self.info, account, mint_info],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`mint_to`] instruction
+    pub fn mint_to<'a>(
+        &self,
+        cpi: impl CPI,
+        mint: &MintAccount<AI>,
+        account: &TokenAccount<AI>,
+        mint_authority: &AI,
+        amount: u64,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let mint_info = mint.info();
+        let account_info = account.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &mint_to(
+                &spl_token::ID,
+                mint_info.key(),
+                account_info.key(),
+                mint_authority.key(),
+                &[mint_authority.key()],
+                amount,
+            )?,
+            &[&self.info, mint_info, account_info, mint_authority],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`mint_to_checked`] instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_to_checked<'a>(
+        &self,
+        cpi: impl CPI,
+        mint: &MintAccount<AI>,
+        account: &TokenAccount<AI>,
+        mint_authority: &AI,
+        amount: u64,
+        decimals: u8,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let mint_info = mint.info();
+        let account_info = account.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &mint_to_checked(
+                &spl_token::ID,
+                mint_info.key(),
+                account_info.key(),
+                mint_authority.key(),
+                &[mint_authority.key()],
+                amount,
+                decimals,
+            )?,
+            &[&self.info, mint_info, account_info, mint_authority],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`burn`] instruction
+    pub fn burn<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        mint: &MintAccount<AI>,
+        authority: &AI,
+        amount: u64,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        let mint_info = mint.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &burn(
+                &spl_token::ID,
+                account_info.key(),
+                mint_info.key(),
+                authority.key(),
+                &[authority.key()],
+                amount,
+            )?,
+            &[&self.info, account_info, mint_info, authority],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`burn_checked`] instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn burn_checked<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        mint: &MintAccount<AI>,
+        authority: &AI,
+        amount: u64,
+        decimals: u8,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        let mint_info = mint.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &burn_checked(
+                &spl_token::ID,
+                account_info.key(),
+                mint_info.key(),
+                authority.key(),
+                &[authority.key()],
+                amount,
+                decimals,
+            )?,
+            &[&self.info, account_info, mint_info, authority],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`approve`] instruction
+    pub fn approve<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        delegate: &AI,
+        owner: &AI,
+        amount: u64,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &approve(
+                &spl_token::ID,
+                account_info.key(),
+                delegate.key(),
+                owner.key(),
+                &[owner.key()],
+                amount,
+            )?,
+            &[&self.info, account_info, delegate, owner],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`revoke`] instruction
+    pub fn revoke<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        owner: &AI,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &revoke(
+                &spl_token::ID,
+                account_info.key(),
+                owner.key(),
+                &[owner.key()],
+            )?,
+            &[&self.info, account_info, owner],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`freeze_account`] instruction
+    pub fn freeze_account<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        mint: &MintAccount<AI>,
+        freeze_authority: &AI,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        let mint_info = mint.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &freeze_account(
+                &spl_token::ID,
+                account_info.key(),
+                mint_info.key(),
+                freeze_authority.key(),
+                &[freeze_authority.key()],
+            )?,
+            &[&self.info, account_info, mint_info, freeze_authority],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`thaw_account`] instruction
+    pub fn thaw_account<'a>(
+        &self,
+        cpi: impl CPI,
+        account: &TokenAccount<AI>,
+        mint: &MintAccount<AI>,
+        freeze_authority: &AI,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let account_info = account.info();
+        let mint_info = mint.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &thaw_account(
+                &spl_token::ID,
+                account_info.key(),
+                mint_info.key(),
+                freeze_authority.key(),
+                &[freeze_authority.key()],
+            )?,
+            &[&self.info, account_info, mint_info, freeze_authority],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`transfer_checked`] instruction
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_checked<'a>(
+        &self,
+        cpi: impl CPI,
+        from: &TokenAccount<AI>,
+        mint: &MintAccount<AI>,
+        to: &TokenAccount<AI>,
+        authority: &AI,
+        amount: u64,
+        decimals: u8,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        let from_info = from.info();
+        let mint_info = mint.info();
+        let to_info = to.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &transfer_checked(
+                &spl_token::ID,
+                from_info.key(),
+                mint_info.key(),
+                to_info.key(),
+                authority.key(),
+                &[authority.key()],
+                amount,
+                decimals,
+            )?,
+            &[&self.info, from_info, mint_info, to_info, authority],
+            seeds,
+        )
+    }
+
+    /// Calls the token program's [`sync_native`] instruction
+    pub fn sync_native(&self, cpi: impl CPI, account: &TokenAccount<AI>) -> ProgramResult {
+        let account_info = account.info();
+        cpi.invoke(
+            &sync_native(&spl_token::ID, account_info.key())?,
+            &[&self.info, account_info],
+        )
+    }
 }
 impl<AI, T> MultiIndexable<T> for TokenProgram<AI>
 where
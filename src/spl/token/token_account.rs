@@ -9,13 +9,13 @@ use cruiser::account_argument::AccountArgument;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
 
-use crate::spl::token::TokenProgramAccount;
+use crate::spl::token::{token_check, TokenProgramAccount};
 
 // verify_account_arg_impl! {
 //     mod token_account_check<AI>{
 //         <AI> TokenAccount<AI> where AI: AccountInfo{
 //             from: [()];
-//             validate: [(); <'a> Owner<'a>];
+//             validate: [(); <'a> Owner<'a>; <'a> TokenChecks<'a>];
 //             multi: [<I> I where TokenProgramAccount<AI>: MultiIndexable<AI, I>];
 //             single: [<I> I where TokenProgramAccount<AI>: SingleIndexable<AI, I>];
 //         }
@@ -29,6 +29,24 @@ pub struct TokenAccount<AI> {
     /// The account associated
     pub account: TokenProgramAccount<AI>,
 }
+impl<AI> TokenAccount<AI>
+where
+    AI: AccountInfo,
+{
+    /// Creates this without reading the data, used for accounts that are not yet initialized.
+    pub(crate) fn uninitialized(account: TokenProgramAccount<AI>) -> Self {
+        Self {
+            data: spl_token::state::Account::default(),
+            account,
+        }
+    }
+
+    /// Re-reads the data from the account, needed after CPIs that change it.
+    pub fn reload(&mut self) -> CruiserResult<()> {
+        self.data = spl_token::state::Account::unpack(&*self.account.data())?;
+        Ok(())
+    }
+}
 impl<AI> OnChainSize<()> for TokenAccount<AI> {
     fn on_chain_max_size(_arg: ()) -> usize {
         spl_token::state::Account::get_packed_len()
@@ -102,6 +120,106 @@ where
         }
     }
 }
+/// Composable checks for a [`TokenAccount`], [`None`] fields are not checked.
+///
+/// Use with struct update syntax: `TokenChecks { mint: Some(&mint), ..TokenChecks::default() }`
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TokenChecks<'a> {
+    /// The mint of the account
+    pub mint: Option<&'a Pubkey>,
+    /// The owner of the account
+    pub owner: Option<&'a Pubkey>,
+    /// The minimum amount of tokens in the account
+    pub min_amount: Option<u64>,
+    /// The delegate of the account, `Some(None)` requires no delegate
+    pub delegate: Option<Option<&'a Pubkey>>,
+    /// The minimum amount delegated
+    pub min_delegated_amount: Option<u64>,
+    /// Requires that the account is not frozen
+    pub not_frozen: bool,
+    /// Whether the account holds native SOL
+    pub is_native: Option<bool>,
+    /// The close authority of the account, `Some(None)` requires no close authority
+    pub close_authority: Option<Option<&'a Pubkey>>,
+}
+impl<AI> ValidateArgument<TokenChecks<'_>> for TokenAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: TokenChecks) -> CruiserResult<()> {
+        self.validate(program_id, ())?;
+        let key = self.account.key();
+        if let Some(mint) = arg.mint {
+            token_check(key, "mint", &self.data.mint == mint, mint, self.data.mint)?;
+        }
+        if let Some(owner) = arg.owner {
+            token_check(
+                key,
+                "owner",
+                &self.data.owner == owner,
+                owner,
+                self.data.owner,
+            )?;
+        }
+        if let Some(min_amount) = arg.min_amount {
+            token_check(
+                key,
+                "min_amount",
+                self.data.amount >= min_amount,
+                min_amount,
+                self.data.amount,
+            )?;
+        }
+        if let Some(delegate) = arg.delegate {
+            let account_delegate = Option::<Pubkey>::from(self.data.delegate);
+            token_check(
+                key,
+                "delegate",
+                account_delegate.as_ref() == delegate,
+                delegate,
+                account_delegate,
+            )?;
+        }
+        if let Some(min_delegated_amount) = arg.min_delegated_amount {
+            token_check(
+                key,
+                "min_delegated_amount",
+                self.data.delegated_amount >= min_delegated_amount,
+                min_delegated_amount,
+                self.data.delegated_amount,
+            )?;
+        }
+        if arg.not_frozen {
+            token_check(
+                key,
+                "not_frozen",
+                !self.data.is_frozen(),
+                false,
+                self.data.is_frozen(),
+            )?;
+        }
+        if let Some(is_native) = arg.is_native {
+            token_check(
+                key,
+                "is_native",
+                self.data.is_native() == is_native,
+                is_native,
+                self.data.is_native(),
+            )?;
+        }
+        if let Some(close_authority) = arg.close_authority {
+            let account_close_authority = Option::<Pubkey>::from(self.data.close_authority);
+            token_check(
+                key,
+                "close_authority",
+                account_close_authority.as_ref() == close_authority,
+                close_authority,
+                account_close_authority,
+            )?;
+        }
+        Ok(())
+    }
+}
 impl<AI, I> MultiIndexable<I> for TokenAccount<AI>
 where
     AI: AccountInfo,
@@ -128,3 +246,153 @@ where
         self.account.index_info(indexer)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use solana_program::program_option::COption;
+    use spl_token::state::{Account, AccountState};
+
+    use super::*;
+    use crate::program::ProgramKey;
+    use crate::spl::token::TokenProgram;
+    use crate::SolanaAccountInfo;
+
+    fn validate(data: Account, checks: TokenChecks) -> CruiserResult<()> {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let owner = TokenProgram::<()>::KEY;
+        let mut account_data = vec![0; Account::LEN];
+        data.pack_into_slice(&mut account_data);
+        let mut lamports = 0;
+        let info = SolanaAccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut account_data,
+            &owner,
+            false,
+            0,
+        );
+        let mut account: TokenAccount<_> =
+            FromAccounts::from_accounts(&program_id, &mut [info].into_iter(), ())?;
+        account.validate(&program_id, checks)
+    }
+
+    fn assert_failed(result: CruiserResult<()>, check: &str) {
+        let message = result.unwrap_err().message();
+        assert!(
+            message.starts_with(&format!("Token check `{}` failed", check)),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn token_checks_test() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let data = Account {
+            mint,
+            owner,
+            amount: 100,
+            delegate: COption::Some(delegate),
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 50,
+            close_authority: COption::None,
+        };
+        let all = TokenChecks {
+            mint: Some(&mint),
+            owner: Some(&owner),
+            min_amount: Some(100),
+            delegate: Some(Some(&delegate)),
+            min_delegated_amount: Some(50),
+            not_frozen: true,
+            is_native: Some(false),
+            close_authority: Some(None),
+        };
+
+        validate(data, TokenChecks::default()).unwrap();
+        validate(data, all).unwrap();
+        validate(
+            data,
+            TokenChecks {
+                mint: Some(&mint),
+                owner: Some(&owner),
+                ..TokenChecks::default()
+            },
+        )
+        .unwrap();
+
+        for (checks, failed) in [
+            (
+                TokenChecks {
+                    mint: Some(&other),
+                    ..all
+                },
+                "mint",
+            ),
+            (
+                TokenChecks {
+                    owner: Some(&other),
+                    ..all
+                },
+                "owner",
+            ),
+            (
+                TokenChecks {
+                    min_amount: Some(101),
+                    ..all
+                },
+                "min_amount",
+            ),
+            (
+                TokenChecks {
+                    delegate: Some(None),
+                    ..all
+                },
+                "delegate",
+            ),
+            (
+                TokenChecks {
+                    min_delegated_amount: Some(51),
+                    ..all
+                },
+                "min_delegated_amount",
+            ),
+            (
+                TokenChecks {
+                    is_native: Some(true),
+                    ..all
+                },
+                "is_native",
+            ),
+            (
+                TokenChecks {
+                    close_authority: Some(Some(&other)),
+                    ..all
+                },
+                "close_authority",
+            ),
+        ] {
+            assert_failed(validate(data, checks), failed);
+        }
+
+        let frozen = Account {
+            state: AccountState::Frozen,
+            ..data
+        };
+        validate(
+            frozen,
+            TokenChecks {
+                not_frozen: false,
+                ..all
+            },
+        )
+        .unwrap();
+        assert_failed(validate(frozen, all), "not_frozen");
+    }
+}
@@ -0,0 +1,422 @@
+use std::convert::TryInto;
+
+use solana_program::clock::Epoch;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use spl_token::state::{Account, AccountState, Mint};
+
+use crate::{CruiserResult, GenericError};
+
+/// Length of the TLV type and length header
+const TLV_HEADER_SIZE: usize = 4;
+
+/// The type of an extension, stored as the type of a TLV entry
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum ExtensionType {
+    /// Unused space
+    Uninitialized = 0,
+    /// Transfer fee configuration on a mint
+    TransferFeeConfig = 1,
+    /// Withheld transfer fees on a token account
+    TransferFeeAmount = 2,
+    /// Allows closing a mint
+    MintCloseAuthority = 3,
+    /// The default state of new token accounts for a mint
+    DefaultAccountState = 6,
+    /// The owner of a token account cannot be changed
+    ImmutableOwner = 7,
+    /// Requires incoming transfers to a token account to have a memo
+    MemoTransfer = 8,
+}
+impl ExtensionType {
+    /// The length of the extension's value in bytes
+    #[must_use]
+    pub const fn value_len(self) -> usize {
+        match self {
+            ExtensionType::Uninitialized | ExtensionType::ImmutableOwner => 0,
+            ExtensionType::TransferFeeConfig => TransferFeeConfig::LEN,
+            ExtensionType::TransferFeeAmount => TransferFeeAmount::LEN,
+            ExtensionType::MintCloseAuthority => MintCloseAuthority::LEN,
+            ExtensionType::DefaultAccountState => DefaultAccountState::LEN,
+            ExtensionType::MemoTransfer => MemoTransfer::LEN,
+        }
+    }
+
+    /// The size of an account or mint with the given extensions
+    #[must_use]
+    pub fn account_size(extensions: &[ExtensionType]) -> usize {
+        if extensions.is_empty() {
+            // Mints without extensions are only `Mint::LEN` but have no account type to be mistaken for
+            Account::LEN
+        } else {
+            Account::LEN
+                + 1
+                + extensions
+                    .iter()
+                    .map(|extension| TLV_HEADER_SIZE + extension.value_len())
+                    .sum::<usize>()
+        }
+    }
+}
+
+/// The type of an extended Token-2022 account, stored after the base state
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum AccountType {
+    /// A mint
+    Mint = 1,
+    /// A token account
+    Account = 2,
+}
+impl AccountType {
+    /// The length of the base state without extensions
+    #[must_use]
+    pub fn base_len(self) -> usize {
+        match self {
+            AccountType::Mint => Mint::LEN,
+            AccountType::Account => Account::LEN,
+        }
+    }
+}
+
+/// A Token-2022 extension that can be read from the TLV area of an account or mint
+pub trait TokenExtension: Sized {
+    /// The type of the extension
+    const TYPE: ExtensionType;
+    /// The length of the extension's value in bytes
+    const LEN: usize;
+
+    /// Reads the extension from its value bytes
+    fn unpack(data: &[u8]) -> CruiserResult<Self>;
+}
+
+/// The extensions of a Token-2022 account or mint, borrowed from after the base state.
+/// Entries are parsed on access rather than up front.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Extensions<'a> {
+    tlv_data: &'a [u8],
+}
+impl<'a> Extensions<'a> {
+    /// Borrows the extension area from the full data of an account of type `account_type`.
+    /// Data of exactly the base length gives no extensions.
+    pub fn from_account_data(data: &'a [u8], account_type: AccountType) -> CruiserResult<Self> {
+        if data.len() == account_type.base_len() {
+            return Ok(Self::default());
+        }
+        // Extended mints are padded to the account length before the account type
+        match data.get(Account::LEN) {
+            Some(&found) if found == account_type as u8 => Ok(Self {
+                tlv_data: &data[Account::LEN + 1..],
+            }),
+            _ => Err(GenericError::CouldNotDeserialize {
+                what: format!("{:?} with data length {}", account_type, data.len()),
+            }
+            .into()),
+        }
+    }
+
+    /// Iterates over the extension types present
+    pub fn types(self) -> impl Iterator<Item = CruiserResult<u16>> + 'a {
+        TlvIter {
+            data: self.tlv_data,
+        }
+        .map(|entry| entry.map(|(extension_type, _)| extension_type))
+    }
+
+    /// Gets the raw value of an extension type
+    pub fn get_raw(self, extension_type: ExtensionType) -> CruiserResult<Option<&'a [u8]>> {
+        for entry in (TlvIter {
+            data: self.tlv_data,
+        }) {
+            let (entry_type, value) = entry?;
+            if entry_type == extension_type as u16 {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Gets an extension, [`None`] if not present
+    pub fn get<E>(self) -> CruiserResult<Option<E>>
+    where
+        E: TokenExtension,
+    {
+        self.get_raw(E::TYPE)?.map(E::unpack).transpose()
+    }
+}
+
+struct TlvIter<'a> {
+    data: &'a [u8],
+}
+impl<'a> Iterator for TlvIter<'a> {
+    type Item = CruiserResult<(u16, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < TLV_HEADER_SIZE {
+            return None;
+        }
+        let extension_type = u16::from_le_bytes([self.data[0], self.data[1]]);
+        if extension_type == ExtensionType::Uninitialized as u16 {
+            return None;
+        }
+        let len = u16::from_le_bytes([self.data[2], self.data[3]]) as usize;
+        if let Some(value) = self.data.get(TLV_HEADER_SIZE..TLV_HEADER_SIZE + len) {
+            self.data = &self.data[TLV_HEADER_SIZE + len..];
+            Some(Ok((extension_type, value)))
+        } else {
+            let remaining = self.data.len() - TLV_HEADER_SIZE;
+            self.data = &[];
+            Some(Err(GenericError::NotEnoughData {
+                needed: len,
+                remaining,
+            }
+            .into()))
+        }
+    }
+}
+
+fn check_len<E: TokenExtension>(data: &[u8]) -> CruiserResult<()> {
+    if data.len() == E::LEN {
+        Ok(())
+    } else {
+        Err(GenericError::CouldNotDeserialize {
+            what: format!("{:?} extension of length {}", E::TYPE, data.len()),
+        }
+        .into())
+    }
+}
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+fn read_optional_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    let key = Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap());
+    if key == Pubkey::default() {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+/// A transfer fee for a range of epochs
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransferFee {
+    /// The first epoch the fee is active
+    pub epoch: Epoch,
+    /// The maximum fee for a single transfer
+    pub maximum_fee: u64,
+    /// The fee in basis points of the transfer amount
+    pub transfer_fee_basis_points: u16,
+}
+impl TransferFee {
+    const LEN: usize = 18;
+
+    fn unpack(data: &[u8]) -> Self {
+        Self {
+            epoch: read_u64(data, 0),
+            maximum_fee: read_u64(data, 8),
+            transfer_fee_basis_points: u16::from_le_bytes([data[16], data[17]]),
+        }
+    }
+
+    /// Calculates the fee for transferring `amount`, [`None`] on overflow
+    #[must_use]
+    pub fn calculate_fee(&self, amount: u64) -> Option<u64> {
+        if self.transfer_fee_basis_points == 0 || amount == 0 {
+            return Some(0);
+        }
+        let numerator =
+            u128::from(amount).checked_mul(u128::from(self.transfer_fee_basis_points))?;
+        let fee = (numerator + 9_999) / 10_000;
+        Some(fee.min(u128::from(self.maximum_fee)) as u64)
+    }
+}
+
+/// Transfer fee configuration on a mint
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    /// Can change the transfer fee
+    pub transfer_fee_config_authority: Option<Pubkey>,
+    /// Can withdraw withheld fees
+    pub withdraw_withheld_authority: Option<Pubkey>,
+    /// Fees withheld on the mint
+    pub withheld_amount: u64,
+    /// The fee for epochs before `newer_transfer_fee.epoch`
+    pub older_transfer_fee: TransferFee,
+    /// The fee starting at `newer_transfer_fee.epoch`
+    pub newer_transfer_fee: TransferFee,
+}
+impl TransferFeeConfig {
+    /// Gets the transfer fee active in `epoch`
+    #[must_use]
+    pub fn get_epoch_fee(&self, epoch: Epoch) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+
+    /// Calculates the fee for transferring `amount` in `epoch`, [`None`] on overflow
+    #[must_use]
+    pub fn calculate_epoch_fee(&self, epoch: Epoch, amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch).calculate_fee(amount)
+    }
+}
+impl TokenExtension for TransferFeeConfig {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeConfig;
+    const LEN: usize = 32 + 32 + 8 + TransferFee::LEN * 2;
+
+    fn unpack(data: &[u8]) -> CruiserResult<Self> {
+        check_len::<Self>(data)?;
+        Ok(Self {
+            transfer_fee_config_authority: read_optional_pubkey(data, 0),
+            withdraw_withheld_authority: read_optional_pubkey(data, 32),
+            withheld_amount: read_u64(data, 64),
+            older_transfer_fee: TransferFee::unpack(&data[72..72 + TransferFee::LEN]),
+            newer_transfer_fee: TransferFee::unpack(&data[72 + TransferFee::LEN..]),
+        })
+    }
+}
+
+/// Transfer fees withheld on a token account
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransferFeeAmount {
+    /// The withheld fees
+    pub withheld_amount: u64,
+}
+impl TokenExtension for TransferFeeAmount {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeAmount;
+    const LEN: usize = 8;
+
+    fn unpack(data: &[u8]) -> CruiserResult<Self> {
+        check_len::<Self>(data)?;
+        Ok(Self {
+            withheld_amount: read_u64(data, 0),
+        })
+    }
+}
+
+/// The authority that can close a mint
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MintCloseAuthority {
+    /// The close authority
+    pub close_authority: Option<Pubkey>,
+}
+impl TokenExtension for MintCloseAuthority {
+    const TYPE: ExtensionType = ExtensionType::MintCloseAuthority;
+    const LEN: usize = 32;
+
+    fn unpack(data: &[u8]) -> CruiserResult<Self> {
+        check_len::<Self>(data)?;
+        Ok(Self {
+            close_authority: read_optional_pubkey(data, 0),
+        })
+    }
+}
+
+/// The state new token accounts of a mint start in
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DefaultAccountState {
+    /// The default state
+    pub state: AccountState,
+}
+impl TokenExtension for DefaultAccountState {
+    const TYPE: ExtensionType = ExtensionType::DefaultAccountState;
+    const LEN: usize = 1;
+
+    fn unpack(data: &[u8]) -> CruiserResult<Self> {
+        check_len::<Self>(data)?;
+        let state = match data[0] {
+            0 => AccountState::Uninitialized,
+            1 => AccountState::Initialized,
+            2 => AccountState::Frozen,
+            state => {
+                return Err(GenericError::CouldNotDeserialize {
+                    what: format!("account state {}", state),
+                }
+                .into())
+            }
+        };
+        Ok(Self { state })
+    }
+}
+
+/// Whether incoming transfers to a token account require a memo
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoTransfer {
+    /// Requires incoming transfers to have a memo
+    pub require_incoming_transfer_memos: bool,
+}
+impl TokenExtension for MemoTransfer {
+    const TYPE: ExtensionType = ExtensionType::MemoTransfer;
+    const LEN: usize = 1;
+
+    fn unpack(data: &[u8]) -> CruiserResult<Self> {
+        check_len::<Self>(data)?;
+        Ok(Self {
+            require_incoming_transfer_memos: data[0] != 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extensions_test() {
+        let mut data = vec![0; Account::LEN];
+        data.push(AccountType::Mint as u8);
+        data.extend_from_slice(&(ExtensionType::MintCloseAuthority as u16).to_le_bytes());
+        data.extend_from_slice(&(MintCloseAuthority::LEN as u16).to_le_bytes());
+        let close_authority = Pubkey::new_unique();
+        data.extend_from_slice(close_authority.as_ref());
+        data.extend_from_slice(&(ExtensionType::MemoTransfer as u16).to_le_bytes());
+        data.extend_from_slice(&(MemoTransfer::LEN as u16).to_le_bytes());
+        data.push(1);
+        assert_eq!(
+            data.len(),
+            ExtensionType::account_size(&[
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::MemoTransfer
+            ])
+        );
+
+        let extensions = Extensions::from_account_data(&data, AccountType::Mint).unwrap();
+        assert_eq!(
+            extensions.get::<MintCloseAuthority>().unwrap(),
+            Some(MintCloseAuthority {
+                close_authority: Some(close_authority)
+            })
+        );
+        assert_eq!(
+            extensions.get::<MemoTransfer>().unwrap(),
+            Some(MemoTransfer {
+                require_incoming_transfer_memos: true
+            })
+        );
+        assert_eq!(extensions.get::<TransferFeeAmount>().unwrap(), None);
+        assert!(Extensions::from_account_data(&data, AccountType::Account).is_err());
+        assert!(
+            Extensions::from_account_data(&data[..Mint::LEN], AccountType::Mint)
+                .unwrap()
+                .get::<MintCloseAuthority>()
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn transfer_fee_test() {
+        let fee = TransferFee {
+            epoch: 0,
+            maximum_fee: 100,
+            transfer_fee_basis_points: 50,
+        };
+        assert_eq!(fee.calculate_fee(0), Some(0));
+        assert_eq!(fee.calculate_fee(1), Some(1));
+        assert_eq!(fee.calculate_fee(10_000), Some(50));
+        assert_eq!(fee.calculate_fee(1_000_000), Some(100));
+    }
+}
@@ -0,0 +1,139 @@
+use std::ops::Deref;
+
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::spl::token_2022::{AccountType, AnyTokenProgramAccount, Extensions, TokenExtension};
+use crate::{AccountInfo, CruiserResult, GenericError};
+
+// verify_account_arg_impl! {
+//     mod any_mint_account_check<AI>{
+//         <AI> AnyMintAccount<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [()];
+//             multi: [<I> I where AnyTokenProgramAccount<AI>: MultiIndexable<AI, I>];
+//             single: [<I> I where AnyTokenProgramAccount<AI>: SingleIndexable<AI, I>];
+//         }
+//     }
+// }
+
+/// A mint owned by either the token program or the Token-2022 program.
+/// Token-2022 extensions can be read with [`AnyMintAccount::extension`].
+#[derive(Debug)]
+pub struct AnyMintAccount<AI> {
+    data: spl_token::state::Mint,
+    /// The account associated
+    pub account: AnyTokenProgramAccount<AI>,
+}
+impl<AI> AnyMintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    /// The extension types of this mint, empty for token program mints.
+    /// Parsed from the account data on each call.
+    pub fn extension_types(&self) -> CruiserResult<Vec<u16>> {
+        let data = self.account.data();
+        Extensions::from_account_data(&data, AccountType::Mint)?
+            .types()
+            .collect()
+    }
+
+    /// Gets an extension of this mint, [`None`] if not present.
+    /// Parsed from the account data on each call.
+    pub fn extension<E>(&self) -> CruiserResult<Option<E>>
+    where
+        E: TokenExtension,
+    {
+        let data = self.account.data();
+        Extensions::from_account_data(&data, AccountType::Mint)?.get()
+    }
+}
+impl<AI> Deref for AnyMintAccount<AI> {
+    type Target = spl_token::state::Mint;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+impl<AI> AccountArgument for AnyMintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.account.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.account.add_keys(add)
+    }
+}
+impl<AI> FromAccounts<()> for AnyMintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        let account: AnyTokenProgramAccount<AI> =
+            FromAccounts::from_accounts(program_id, infos, arg)?;
+        let account_data = account.data();
+        let base =
+            account_data
+                .get(..spl_token::state::Mint::LEN)
+                .ok_or(GenericError::NotEnoughData {
+                    needed: spl_token::state::Mint::LEN,
+                    remaining: account_data.len(),
+                })?;
+        let data = spl_token::state::Mint::unpack(base)?;
+        // Checks the account type without reading any extensions
+        Extensions::from_account_data(&account_data, AccountType::Mint)?;
+        drop(account_data);
+        Ok(Self { data, account })
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AnyTokenProgramAccount::<AI>::accounts_usage_hint(arg)
+    }
+}
+impl<AI> ValidateArgument<()> for AnyMintAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.account.validate(program_id, arg)
+    }
+}
+impl<AI, I> MultiIndexable<I> for AnyMintAccount<AI>
+where
+    AI: AccountInfo,
+    AnyTokenProgramAccount<AI>: MultiIndexable<I>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.account.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, I> SingleIndexable<I> for AnyMintAccount<AI>
+where
+    AI: AccountInfo,
+    AnyTokenProgramAccount<AI>: SingleIndexable<I, AccountInfo = AI>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.account.index_info(indexer)
+    }
+}
@@ -0,0 +1,137 @@
+//! Implementations for the Token-2022 program.
+//! [`AnyTokenAccount`] and [`AnyMintAccount`] accept accounts of either the [`TokenProgram`](crate::spl::token::TokenProgram) or the [`Token2022Program`].
+
+mod extensions;
+mod mint_account;
+mod program;
+mod token_account;
+
+pub use extensions::*;
+pub use mint_account::*;
+pub use program::*;
+pub use token_account::*;
+
+use crate::account_argument::{
+    AccountArgument, AccountInfoIterator, FromAccounts, MultiIndexable, SingleIndexable,
+    ValidateArgument,
+};
+use crate::program::ProgramKey;
+use crate::{AccountInfo, CruiserResult, GenericError};
+use solana_program::pubkey::Pubkey;
+use std::ops::{Deref, DerefMut};
+
+// verify_account_arg_impl! {
+//     mod any_token_program_account_check <AI>{
+//         <AI> AnyTokenProgramAccount<AI> where AI: AccountInfo {
+//             from: [()];
+//             validate: [()];
+//             multi: [<I> I where AI: MultiIndexable<AI, I>];
+//             single: [<I> I where AI: SingleIndexable<AI, I>];
+//         };
+//     }
+// }
+
+/// Account owned by either the token program or the Token-2022 program
+#[derive(Debug)]
+pub struct AnyTokenProgramAccount<AI>(pub AI);
+impl<AI> AnyTokenProgramAccount<AI>
+where
+    AI: AccountInfo,
+{
+    /// The token program that owns this account
+    #[must_use]
+    pub fn token_program(&self) -> Pubkey {
+        *self.0.owner()
+    }
+
+    /// Returns true if this account is owned by the Token-2022 program
+    #[must_use]
+    pub fn is_token_2022(&self) -> bool {
+        *self.0.owner() == Token2022Program::<()>::KEY
+    }
+}
+impl<AI> Deref for AnyTokenProgramAccount<AI> {
+    type Target = AI;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<AI> DerefMut for AnyTokenProgramAccount<AI> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<AI> AccountArgument for AnyTokenProgramAccount<AI>
+where
+    AI: AccountInfo,
+{
+    type AccountInfo = AI;
+
+    fn write_back(self, program_id: &Pubkey) -> CruiserResult<()> {
+        self.0.write_back(program_id)
+    }
+
+    fn add_keys(&self, add: impl FnMut(Pubkey) -> CruiserResult<()>) -> CruiserResult<()> {
+        self.0.add_keys(add)
+    }
+}
+impl<AI> FromAccounts<()> for AnyTokenProgramAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn from_accounts(
+        program_id: &Pubkey,
+        infos: &mut impl AccountInfoIterator<Item = AI>,
+        arg: (),
+    ) -> CruiserResult<Self> {
+        Ok(Self(AI::from_accounts(program_id, infos, arg)?))
+    }
+
+    fn accounts_usage_hint(arg: &()) -> (usize, Option<usize>) {
+        AI::accounts_usage_hint(arg)
+    }
+}
+impl<AI> ValidateArgument<()> for AnyTokenProgramAccount<AI>
+where
+    AI: AccountInfo,
+{
+    fn validate(&mut self, program_id: &Pubkey, arg: ()) -> CruiserResult<()> {
+        self.0.validate(program_id, arg)?;
+        let owner = *self.0.owner();
+        if owner == spl_token::ID || owner == Token2022Program::<()>::KEY {
+            Ok(())
+        } else {
+            Err(GenericError::AccountOwnerNotEqual {
+                account: *self.0.key(),
+                owner,
+                expected_owner: vec![spl_token::ID, Token2022Program::<()>::KEY],
+            }
+            .into())
+        }
+    }
+}
+impl<AI, I> MultiIndexable<I> for AnyTokenProgramAccount<AI>
+where
+    AI: AccountInfo + MultiIndexable<I>,
+{
+    fn index_is_signer(&self, indexer: I) -> CruiserResult<bool> {
+        self.0.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: I) -> CruiserResult<bool> {
+        self.0.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: I) -> CruiserResult<bool> {
+        self.0.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, I> SingleIndexable<I> for AnyTokenProgramAccount<AI>
+where
+    AI: AccountInfo + SingleIndexable<I>,
+{
+    fn index_info(&self, indexer: I) -> CruiserResult<&AI> {
+        self.0.index_info(indexer)
+    }
+}
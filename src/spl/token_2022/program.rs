@@ -0,0 +1,214 @@
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey;
+use solana_program::pubkey::Pubkey;
+
+use crate::account_argument::{AccountArgument, MultiIndexable, Single, SingleIndexable};
+use crate::cpi::CPI;
+use crate::pda_seeds::PDASeedSet;
+use crate::program::{Program, ProgramKey};
+use crate::spl::token_2022::{AnyMintAccount, AnyTokenAccount, AnyTokenProgramAccount};
+use crate::{
+    AccountInfo, CruiserResult, SolanaAccountMeta, SolanaInstruction, ToSolanaAccountInfo,
+};
+
+// verify_account_arg_impl! {
+//     mod token_2022_program_check<AI>{
+//         <AI> Token2022Program<AI> where AI: AccountInfo{
+//             from: [()];
+//             validate: [()];
+//             multi: [(); AllAny];
+//             single: [()];
+//         };
+//     }
+// }
+
+/// Instruction discriminant of `TransferChecked`
+const TRANSFER_CHECKED: u8 = 12;
+/// Instruction discriminant of the transfer fee extension instructions
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+/// Transfer fee extension sub-instruction discriminant of `TransferCheckedWithFee`
+const TRANSFER_CHECKED_WITH_FEE: u8 = 1;
+
+/// Creates a `TransferChecked` instruction for `token_program`, either the token program or the Token-2022 program
+#[must_use]
+pub fn transfer_checked_instruction(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> SolanaInstruction {
+    let mut data = Vec::with_capacity(10);
+    data.push(TRANSFER_CHECKED);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    SolanaInstruction {
+        program_id: *token_program,
+        accounts: transfer_accounts(source, mint, destination, authority),
+        data,
+    }
+}
+
+/// Creates a Token-2022 `TransferCheckedWithFee` instruction, `fee` must match the mint's transfer fee
+#[must_use]
+pub fn transfer_checked_with_fee_instruction(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+) -> SolanaInstruction {
+    let mut data = Vec::with_capacity(19);
+    data.push(TRANSFER_FEE_EXTENSION);
+    data.push(TRANSFER_CHECKED_WITH_FEE);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    data.extend_from_slice(&fee.to_le_bytes());
+    SolanaInstruction {
+        program_id: Token2022Program::<()>::KEY,
+        accounts: transfer_accounts(source, mint, destination, authority),
+        data,
+    }
+}
+
+/// Errors with [`ProgramError::IncorrectProgramId`] unless every account is owned by the Token-2022 program
+fn check_token_2022<AI>(accounts: &[&AnyTokenProgramAccount<AI>]) -> ProgramResult
+where
+    AI: AccountInfo,
+{
+    if accounts.iter().all(|account| account.is_token_2022()) {
+        Ok(())
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+fn transfer_accounts(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+) -> Vec<SolanaAccountMeta> {
+    vec![
+        SolanaAccountMeta::new(*source, false),
+        SolanaAccountMeta::new_readonly(*mint, false),
+        SolanaAccountMeta::new(*destination, false),
+        SolanaAccountMeta::new_readonly(*authority, true),
+    ]
+}
+
+/// The SPL Token-2022 Program.
+#[derive(AccountArgument, Debug, Clone)]
+#[account_argument(account_info = AI, generics = [where AI: AccountInfo])]
+pub struct Token2022Program<AI> {
+    /// The program's info
+    #[validate(key = &Self::KEY)]
+    pub info: AI,
+}
+impl<AI> ProgramKey for Token2022Program<AI> {
+    const KEY: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+}
+impl<AI> Program for Token2022Program<AI> where AI: AccountInfo {}
+impl<'b, AI> Token2022Program<AI>
+where
+    AI: ToSolanaAccountInfo<'b>,
+{
+    /// Calls the Token-2022 program's `TransferChecked` instruction.
+    /// Errors if `from`, `mint` or `to` is not owned by the Token-2022 program, use [`TokenProgram::transfer_checked`](crate::spl::token::TokenProgram::transfer_checked) for token program accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_checked<'a>(
+        &self,
+        cpi: impl CPI,
+        from: &AnyTokenAccount<AI>,
+        mint: &AnyMintAccount<AI>,
+        to: &AnyTokenAccount<AI>,
+        authority: &AI,
+        amount: u64,
+        decimals: u8,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        check_token_2022(&[&from.account, &mint.account, &to.account])?;
+        let from_info = from.info();
+        let mint_info = mint.info();
+        let to_info = to.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &transfer_checked_instruction(
+                &from.account.token_program(),
+                from_info.key(),
+                mint_info.key(),
+                to_info.key(),
+                authority.key(),
+                amount,
+                decimals,
+            ),
+            &[&self.info, from_info, mint_info, to_info, authority],
+            seeds,
+        )
+    }
+
+    /// Calls the Token-2022 program's `TransferCheckedWithFee` instruction.
+    /// `fee` can be calculated with [`TransferFeeConfig::calculate_epoch_fee`](crate::spl::token_2022::TransferFeeConfig::calculate_epoch_fee).
+    /// Errors if `from`, `mint` or `to` is not owned by the Token-2022 program.
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_checked_with_fee<'a>(
+        &self,
+        cpi: impl CPI,
+        from: &AnyTokenAccount<AI>,
+        mint: &AnyMintAccount<AI>,
+        to: &AnyTokenAccount<AI>,
+        authority: &AI,
+        amount: u64,
+        decimals: u8,
+        fee: u64,
+        seeds: impl IntoIterator<Item = &'a PDASeedSet<'a>>,
+    ) -> ProgramResult {
+        check_token_2022(&[&from.account, &mint.account, &to.account])?;
+        let from_info = from.info();
+        let mint_info = mint.info();
+        let to_info = to.info();
+        PDASeedSet::invoke_signed_multiple(
+            cpi,
+            &transfer_checked_with_fee_instruction(
+                from_info.key(),
+                mint_info.key(),
+                to_info.key(),
+                authority.key(),
+                amount,
+                decimals,
+                fee,
+            ),
+            &[&self.info, from_info, mint_info, to_info, authority],
+            seeds,
+        )
+    }
+}
+impl<AI, T> MultiIndexable<T> for Token2022Program<AI>
+where
+    AI: AccountInfo + MultiIndexable<T>,
+{
+    fn index_is_signer(&self, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_signer(indexer)
+    }
+
+    fn index_is_writable(&self, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_writable(indexer)
+    }
+
+    fn index_is_owner(&self, owner: &Pubkey, indexer: T) -> CruiserResult<bool> {
+        self.info.index_is_owner(owner, indexer)
+    }
+}
+impl<AI, T> SingleIndexable<T> for Token2022Program<AI>
+where
+    AI: AccountInfo + SingleIndexable<T>,
+{
+    fn index_info(&self, indexer: T) -> CruiserResult<&AI> {
+        self.info.index_info(indexer)
+    }
+}
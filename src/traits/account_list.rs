@@ -1,21 +1,37 @@
 //! Account types list of a program.
 
+use std::io::Write;
 use std::num::NonZeroU64;
 
 pub use cruiser_derive::AccountList;
 
 use crate::compressed_numbers::CompressedNumber;
+use crate::CruiserResult;
 
 /// A list of all accounts used by a program.
 pub trait AccountList {
     /// The compression algorithm
     type DiscriminantCompressed: CompressedNumber<Num = NonZeroU64>;
 }
+/// An account list that can read and write any of its account types based on the discriminant.
+/// Implemented by the [`AccountList`](derive@AccountList) derive with `#[account_list(decode)]`.
+pub trait AccountListDecode: AccountList + Sized {
+    /// Deserializes the account type with `discriminant` from `data`, [`None`] if no account type has `discriminant`
+    fn decode(discriminant: NonZeroU64, data: &mut &[u8]) -> CruiserResult<Option<Self>>;
+    /// The discriminant of the contained account type
+    #[must_use]
+    fn variant_discriminant(&self) -> NonZeroU64;
+    /// Serializes the contained account without its discriminant
+    fn encode(&self, writer: &mut impl Write) -> CruiserResult<()>;
+}
 /// Allows an account list to support an account type
 ///
 /// # Safety
 /// Implementor must guarantee that no two discriminates match
 pub unsafe trait AccountListItem<T>: Sized + AccountList {
+    /// The [compressed discriminant](AccountListItem::compressed_discriminant) as bytes, evaluated at compile time with [`ConstCompressedNumber`](crate::compressed_numbers::ConstCompressedNumber)
+    const DISCRIMINANT_BYTES: &'static [u8];
+
     /// The discriminant of the account type
     #[must_use]
     fn discriminant() -> NonZeroU64;
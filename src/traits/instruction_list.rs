@@ -21,6 +21,8 @@ pub trait InstructionList: Copy {
     fn discriminant_compressed(self) -> Self::DiscriminantCompressed {
         Self::DiscriminantCompressed::from_number(self.discriminant())
     }
+    /// Gets the compressed discriminant as bytes, evaluated at compile time with [`ConstCompressedNumber`](crate::compressed_numbers::ConstCompressedNumber)
+    fn discriminant_bytes(self) -> &'static [u8];
     /// Creates the instruction from a discriminant
     fn from_discriminant(discriminant: u64) -> Option<Self>;
 }
@@ -1,85 +1,46 @@
 //! Small size vectors for additional space savings than the. Still experimental.
 
-use std::convert::TryFrom;
-use std::io::Write;
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{Debug, Formatter};
+use std::io::{Error, ErrorKind, Write};
+use std::marker::PhantomData;
 use std::ops::{Deref, Index, IndexMut};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
 use crate::account_argument::AccountArgument;
+use crate::compressed_numbers::CompressedNumber;
 use crate::util::bytes_ext::{ReadExt, WriteExt};
 use crate::{CruiserResult, GenericError, Pubkey};
 
-macro_rules! small_vec {
-    ($ident:ident, $ty:ty, $write:ident, $read:ident, $docs:expr) => {
-        #[derive(Debug, Clone, PartialEq, Eq)]
-        #[doc=$docs]
-        pub struct $ident<T>(Vec<T>);
-        impl<T> TryFrom<Vec<T>> for $ident<T> {
-            type Error = GenericError;
-
-            fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
-                if <$ty>::try_from(value.len()).is_ok() {
-                    Ok(Self(value))
-                } else {
-                    Err(GenericError::SizeInvalid {
-                        min: 0,
-                        max: <$ty>::MAX as usize,
-                        value: value.len(),
-                    })
-                }
-            }
-        }
-        impl<T> From<$ident<T>> for Vec<T> {
-            fn from(from: $ident<T>) -> Self {
+/// Impls shared by all the small vectors, `$default` constructs an empty vector
+macro_rules! vec_impls {
+    ([$($gen:ident),*] $ty:ty, $default:expr) => {
+        impl<$($gen),*> From<$ty> for Vec<T> {
+            fn from(from: $ty) -> Self {
                 from.0
             }
         }
-        impl<T> Deref for $ident<T> {
+        impl<$($gen),*> Deref for $ty {
             type Target = Vec<T>;
 
             fn deref(&self) -> &Self::Target {
                 &self.0
             }
         }
-        impl<T> Index<usize> for $ident<T> {
+        impl<$($gen),*> Index<usize> for $ty {
             type Output = <Vec<T> as Index<usize>>::Output;
 
             fn index(&self, index: usize) -> &Self::Output {
                 self.0.index(index)
             }
         }
-        impl<T> IndexMut<usize> for $ident<T> {
+        impl<$($gen),*> IndexMut<usize> for $ty {
             fn index_mut(&mut self, index: usize) -> &mut Self::Output {
                 self.0.index_mut(index)
             }
         }
-        impl<T> BorshSerialize for $ident<T>
-        where
-            T: BorshSerialize,
-        {
-            fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-                writer.$write(self.len() as $ty)?;
-                for val in self.iter() {
-                    val.serialize(writer)?;
-                }
-                Ok(())
-            }
-        }
-        impl<T> BorshDeserialize for $ident<T>
-        where
-            T: BorshDeserialize,
-        {
-            fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
-                let len = buf.$read()?;
-                let mut out = Vec::with_capacity(len as usize);
-                for _ in 0..len {
-                    out.push(T::deserialize(buf)?);
-                }
-                Ok(Self(out))
-            }
-        }
-        impl<T> AccountArgument for $ident<T>
+        impl<$($gen),*> AccountArgument for $ty
         where
             T: AccountArgument,
         {
@@ -102,7 +63,7 @@ macro_rules! small_vec {
                 Ok(())
             }
         }
-        impl<T> IntoIterator for $ident<T> {
+        impl<$($gen),*> IntoIterator for $ty {
             type Item = <Vec<T> as IntoIterator>::Item;
             type IntoIter = <Vec<T> as IntoIterator>::IntoIter;
 
@@ -110,27 +71,76 @@ macro_rules! small_vec {
                 self.0.into_iter()
             }
         }
-        impl<'a, T> IntoIterator for &'a $ident<T> {
+        impl<'a, $($gen),*> IntoIterator for &'a $ty {
             type Item = <&'a Vec<T> as IntoIterator>::Item;
             type IntoIter = <&'a Vec<T> as IntoIterator>::IntoIter;
 
             fn into_iter(self) -> Self::IntoIter {
-                (&self.0).into_iter()
+                self.0.iter()
             }
         }
-        impl<'a, T> IntoIterator for &'a mut $ident<T> {
+        impl<'a, $($gen),*> IntoIterator for &'a mut $ty {
             type Item = <&'a mut Vec<T> as IntoIterator>::Item;
             type IntoIter = <&'a mut Vec<T> as IntoIterator>::IntoIter;
 
             fn into_iter(self) -> Self::IntoIter {
-                (&mut self.0).into_iter()
+                self.0.iter_mut()
             }
         }
-        impl<T> Default for $ident<T> {
+        impl<$($gen),*> Default for $ty {
             fn default() -> Self {
-                Self(vec![])
+                $default
+            }
+        }
+    };
+}
+
+macro_rules! small_vec {
+    ($ident:ident, $ty:ty, $write:ident, $read:ident, $docs:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[doc=$docs]
+        pub struct $ident<T>(Vec<T>);
+        impl<T> TryFrom<Vec<T>> for $ident<T> {
+            type Error = GenericError;
+
+            fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+                if <$ty>::try_from(value.len()).is_ok() {
+                    Ok(Self(value))
+                } else {
+                    Err(GenericError::SizeInvalid {
+                        min: 0,
+                        max: <$ty>::MAX as usize,
+                        value: value.len(),
+                    })
+                }
+            }
+        }
+        impl<T> BorshSerialize for $ident<T>
+        where
+            T: BorshSerialize,
+        {
+            fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                writer.$write(self.len() as $ty)?;
+                for val in &**self {
+                    val.serialize(writer)?;
+                }
+                Ok(())
+            }
+        }
+        impl<T> BorshDeserialize for $ident<T>
+        where
+            T: BorshDeserialize,
+        {
+            fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                let len = buf.$read()?;
+                let mut out = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    out.push(T::deserialize(buf)?);
+                }
+                Ok(Self(out))
             }
         }
+        vec_impls!([T] $ident<T>, Self(vec![]));
     };
 }
 
@@ -149,6 +159,86 @@ small_vec!(
     "A vector with max size in a u16"
 );
 
+/// A vector with its length stored as the [`CompressedNumber`] `L`, such as [`Leb128<u32>`](crate::compressed_numbers::Leb128).
+pub struct CompressedVec<L, T>(Vec<T>, PhantomData<fn() -> L>);
+impl<L, T> TryFrom<Vec<T>> for CompressedVec<L, T>
+where
+    L: CompressedNumber,
+    L::Num: TryFrom<usize>,
+{
+    type Error = GenericError;
+
+    fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+        if L::Num::try_from(value.len()).is_ok() {
+            Ok(Self(value, PhantomData))
+        } else {
+            Err(GenericError::Custom {
+                error: format!("Length `{}` does not fit the length type", value.len()),
+            })
+        }
+    }
+}
+impl<L, T> Debug for CompressedVec<L, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CompressedVec").field(&self.0).finish()
+    }
+}
+impl<L, T> Clone for CompressedVec<L, T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+impl<L, T> PartialEq for CompressedVec<L, T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<L, T> Eq for CompressedVec<L, T> where T: Eq {}
+impl<L, T> BorshSerialize for CompressedVec<L, T>
+where
+    L: CompressedNumber,
+    L::Num: TryFrom<usize>,
+    T: BorshSerialize,
+{
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let len = L::Num::try_from(self.len())
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "Length does not fit"))?;
+        L::from_number(len).serialize(writer)?;
+        for val in &**self {
+            val.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+impl<L, T> BorshDeserialize for CompressedVec<L, T>
+where
+    L: CompressedNumber,
+    L::Num: TryInto<usize>,
+    T: BorshDeserialize,
+{
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let len: usize = L::deserialize(buf)?
+            .into_number()
+            .try_into()
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Length does not fit `usize`"))?;
+        let mut out = Vec::with_capacity(len.min(buf.len()));
+        for _ in 0..len {
+            out.push(T::deserialize(buf)?);
+        }
+        Ok(Self(out, PhantomData))
+    }
+}
+vec_impls!([L, T] CompressedVec<L, T>, Self(vec![], PhantomData));
+
 #[cfg(test)]
 mod test {
     use std::convert::TryInto;
@@ -156,6 +246,7 @@ mod test {
     use rand::{thread_rng, Rng};
 
     use super::*;
+    use crate::compressed_numbers::{Leb128, ZeroCount};
 
     #[test]
     fn vec8_test() {
@@ -194,4 +285,28 @@ mod test {
             assert_eq!(small_vec, deserialized);
         }
     }
+
+    #[test]
+    fn compressed_vec_test() {
+        let mut rand = thread_rng();
+        for len in (0..1 << 16).step_by(1 << 8).chain([127, 128, 16383, 16384]) {
+            let vec: Vec<u8> = (0..len).map(|_| rand.gen()).collect();
+            let leb_vec: CompressedVec<Leb128<u32>, _> = vec.clone().try_into().unwrap();
+            let bytes = BorshSerialize::try_to_vec(&leb_vec).expect("Could not serialize");
+            assert_eq!(
+                bytes.len(),
+                len + CompressedNumber::num_bytes(Leb128::<u32>::from_number(len as u32))
+            );
+            let deserialized: CompressedVec<Leb128<u32>, _> =
+                BorshDeserialize::try_from_slice(&bytes).expect("Could not deserialize");
+            assert_eq!(leb_vec, deserialized);
+
+            let zero_vec: CompressedVec<ZeroCount<u64>, _> = vec.try_into().unwrap();
+            let bytes = BorshSerialize::try_to_vec(&zero_vec).expect("Could not serialize");
+            let deserialized: CompressedVec<ZeroCount<u64>, _> =
+                BorshDeserialize::try_from_slice(&bytes).expect("Could not deserialize");
+            assert_eq!(zero_vec, deserialized);
+        }
+        assert!(CompressedVec::<Leb128<u16>, u8>::try_from(vec![0; 1 << 16]).is_err());
+    }
 }
@@ -1,7 +1,10 @@
 //! Assertions used in generated code and standard types.
 
 use crate::account_argument::{MultiIndexable, SingleIndexable};
+use crate::account_list::AccountListItem;
+use crate::compressed_numbers::CompressedNumber;
 use crate::{AccountInfo, CruiserResult, GenericError};
+use borsh::BorshDeserialize;
 use solana_program::pubkey::Pubkey;
 use std::fmt::Debug;
 
@@ -88,3 +91,24 @@ where
         .into())
     }
 }
+
+/// Asserts that `info`'s data starts with [`AL::DISCRIMINANT_BYTES`](AccountListItem::DISCRIMINANT_BYTES).
+pub fn assert_discriminant<AI, AL, D>(info: &AI) -> CruiserResult<()>
+where
+    AI: AccountInfo,
+    AL: AccountListItem<D>,
+{
+    let data = info.data();
+    if data.starts_with(AL::DISCRIMINANT_BYTES) {
+        Ok(())
+    } else {
+        Err(GenericError::MismatchedDiscriminant {
+            account: *info.key(),
+            received: AL::DiscriminantCompressed::deserialize(&mut &*data)?
+                .into_number()
+                .get(),
+            expected: AL::discriminant(),
+        }
+        .into())
+    }
+}
@@ -0,0 +1,37 @@
+//! Records the instructions invoked through CPI in tests instead of running them
+
+use std::cell::RefCell;
+use std::sync::Once;
+
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+use crate::{SolanaAccountInfo, SolanaInstruction};
+
+thread_local! {
+    static INVOKED: RefCell<Vec<SolanaInstruction>> = const { RefCell::new(Vec::new()) };
+}
+
+struct RecordingStubs;
+impl SyscallStubs for RecordingStubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &SolanaInstruction,
+        _account_infos: &[SolanaAccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        INVOKED.with(|invoked| invoked.borrow_mut().push(instruction.clone()));
+        Ok(())
+    }
+}
+
+/// Runs `function` returning the instructions it invoked on this thread, which are not executed
+pub fn record_invokes<R>(function: impl FnOnce() -> R) -> (R, Vec<SolanaInstruction>) {
+    static SET_STUBS: Once = Once::new();
+    SET_STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(RecordingStubs));
+    });
+    INVOKED.with(|invoked| invoked.borrow_mut().clear());
+    let out = function();
+    (out, INVOKED.with(RefCell::take))
+}
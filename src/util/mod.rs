@@ -16,9 +16,38 @@ use crate::{CruiserResult, GenericError};
 
 pub mod assert;
 pub(crate) mod bytes_ext;
+#[cfg(test)]
+pub(crate) mod invoke_recorder;
 pub mod short_iter;
 pub mod short_vec;
 
+/// Checks that `discriminants` are unique and not in `reserved`, used by the [`AccountList`](crate::account_list::AccountList) and [`InstructionList`](crate::instruction_list::InstructionList) derives.
+///
+/// # Panics
+/// Panics if a discriminant is duplicated or reserved, failing compilation when used in a const.
+pub const fn assert_unique_discriminants(discriminants: &[u64], reserved: &[u64]) {
+    let mut index = 0;
+    while index < discriminants.len() {
+        let mut other = index + 1;
+        while other < discriminants.len() {
+            assert!(
+                discriminants[index] != discriminants[other],
+                "Duplicate discriminant"
+            );
+            other += 1;
+        }
+        let mut reserved_index = 0;
+        while reserved_index < reserved.len() {
+            assert!(
+                discriminants[index] != reserved[reserved_index],
+                "Discriminant is reserved"
+            );
+            reserved_index += 1;
+        }
+        index += 1;
+    }
+}
+
 /// A version of [`Cow`](std::borrow::Cow) that only operates as a ref.
 #[derive(Debug, Copy, Clone)]
 pub enum MaybeOwned<'a, T> {
@@ -354,3 +383,29 @@ impl ToNonZero for NonZeroU64 {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::assert_unique_discriminants;
+
+    const _: () = assert_unique_discriminants(&[1, 2, 3], &[4, 5]);
+
+    #[test]
+    fn unique_discriminants_test() {
+        assert_unique_discriminants(&[], &[1]);
+        assert_unique_discriminants(&[1, 3, 2], &[]);
+        assert_unique_discriminants(&[1, 2, 3], &[4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate discriminant")]
+    fn duplicate_discriminant_test() {
+        assert_unique_discriminants(&[1, 2, 1], &[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Discriminant is reserved")]
+    fn reserved_discriminant_test() {
+        assert_unique_discriminants(&[1, 2, 3], &[3]);
+    }
+}